@@ -27,6 +27,7 @@ impl MacroError {
     ///
     /// # Returns
     /// * `proc_macro2::TokenStream` - A token stream representing the error message
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_compile_error(self) -> proc_macro2::TokenStream {
         match self {
             MacroError::Syn(err) => err.to_compile_error(),
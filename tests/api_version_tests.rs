@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Account {
+        id: u32,
+    }
+
+    http_provider!(
+        VersionedProvider,
+        behavior_version: 1,
+        api_version: "2024-06-01",
+        verify_version_header: "x-served-api-version",
+        {
+            {
+                path: "/account",
+                method: GET,
+                fn_name: get_account,
+                res: Account,
+            },
+        }
+    );
+
+    http_provider!(
+        StrictVersionedProvider,
+        behavior_version: 1,
+        api_version: "2024-06-01",
+        verify_version_header: "x-served-api-version",
+        strict_version: true,
+        {
+            {
+                path: "/account",
+                method: GET,
+                fn_name: get_account,
+                res: Account,
+            },
+        }
+    );
+
+    async fn mount_account(mock_server: &MockServer, served_version: &str) {
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .and(header("x-api-version", "2024-06-01"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-served-api-version", served_version)
+                    .set_body_json(Account { id: 7 }),
+            )
+            .expect(1)
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_matching_version_does_not_warn() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount_account(&mock_server, "2024-06-01").await;
+
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let sink = warnings.clone();
+        let provider =
+            VersionedProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_warning_hook(move |warning| sink.lock().unwrap().push(warning.to_string()));
+
+        assert_eq!(provider.get_account().await?, Account { id: 7 });
+        assert!(warnings.lock().unwrap().is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_version_mismatch_warns() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount_account(&mock_server, "2023-01-01").await;
+
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let sink = warnings.clone();
+        let provider =
+            VersionedProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_warning_hook(move |warning| sink.lock().unwrap().push(warning.to_string()));
+
+        assert_eq!(provider.get_account().await?, Account { id: 7 });
+        assert_eq!(
+            *warnings.lock().unwrap(),
+            vec!["API version mismatch: requested 2024-06-01, server reported 2023-01-01"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_version_mismatch_fails_in_strict_mode() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = MockServer::start().await;
+        mount_account(&mock_server, "2023-01-01").await;
+
+        let provider = StrictVersionedProvider::new(
+            Url::from_str(&mock_server.uri())?,
+            Duration::from_secs(5),
+        );
+
+        assert_eq!(
+            provider.get_account().await,
+            Err("API version mismatch: requested 2024-06-01, server reported 2023-01-01".into())
+        );
+
+        Ok(())
+    }
+}
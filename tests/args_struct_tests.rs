@@ -0,0 +1,156 @@
+//! `args_struct` endpoints take their arguments as one generated struct instead of positional
+//! parameters.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, Url};
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_json, header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u32,
+        name: String,
+    }
+
+    #[derive(Serialize)]
+    struct UserPath {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct UserQuery {
+        fields: String,
+    }
+
+    #[derive(Serialize)]
+    struct UpdateUser {
+        name: String,
+    }
+
+    #[derive(Serialize)]
+    struct OrgPath {
+        org: String,
+    }
+
+    http_provider!(
+        UserProvider,
+        behavior_version: 1,
+        {
+            { path: "/users/{id}", method: GET, fn_name: get_user, path_params: UserPath, query_params: UserQuery, headers: HeaderMap, res: User, args_struct: true },
+            { path: "/users/{id}", method: PUT, fn_name: update_user, path_params: UserPath, req: UpdateUser, res: User, args_struct: true },
+            { path: "/export", method: GET, fn_name: export, headers: HeaderMap, range: true, res_format: bytes, args_struct: true },
+            orgs: {
+                prefix: "/orgs/{org}",
+                group_params: OrgPath,
+                { path: "/users/{id}", method: GET, fn_name: get_user, path_params: UserPath, res: User, args_struct: true },
+            },
+        }
+    );
+
+    fn user() -> User {
+        User {
+            id: 7,
+            name: "Ada".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_arguments_are_read_from_the_struct() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/7"))
+            .and(query_param("fields", "name"))
+            .and(header("x-tenant", "acme"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(user()))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/users/7"))
+            .and(body_json(serde_json::json!({ "name": "Ada" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(user()))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/users/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(user()))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url, Duration::from_secs(5));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-tenant", "acme".parse()?);
+        let fetched = provider
+            .get_user(UserProviderGetUserArgs {
+                path: UserPath { id: 7 },
+                query: UserQuery {
+                    fields: "name".to_string(),
+                },
+                headers: Some(headers),
+            })
+            .await?;
+        assert_eq!(fetched, user());
+
+        let updated = provider
+            .update_user(UserProviderUpdateUserArgs {
+                path: UserPath { id: 7 },
+                body: UpdateUser {
+                    name: "Ada".to_string(),
+                },
+            })
+            .await?;
+        assert_eq!(updated, user());
+
+        let fetched = provider
+            .orgs()
+            .get_user(UserProviderOrgsGetUserArgs {
+                orgs_path: OrgPath {
+                    org: "acme".to_string(),
+                },
+                path: UserPath { id: 7 },
+            })
+            .await?;
+        assert_eq!(fetched, user());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_omitted_optional_fields_default() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/export"))
+            .and(header("range", "bytes=0-3"))
+            .respond_with(ResponseTemplate::new(206).set_body_string("part"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/export"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("everything"))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url, Duration::from_secs(5));
+
+        // A struct of optional fields only implements `Default`
+        assert_eq!(
+            provider.export(UserProviderExportArgs::default()).await?,
+            "everything".as_bytes()
+        );
+        let part = provider
+            .export(UserProviderExportArgs {
+                range: Some((0, Some(3))),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(part, "part".as_bytes());
+
+        Ok(())
+    }
+}
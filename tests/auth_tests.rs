@@ -0,0 +1,204 @@
+//! Credentials set on the provider are sent with every request, without an `Authorization`
+//! header threaded through each endpoint, and are kept out of the provider's `Debug` output.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, Url};
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{basic_auth, header, header_exists, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u32,
+    }
+
+    http_provider!(
+        UserProvider,
+        behavior_version: 1,
+        {
+            { path: "/users", method: GET, fn_name: list, res: Vec<User> },
+            { path: "/users", method: POST, fn_name: create, req: User, res: User },
+            { path: "/users", method: GET, fn_name: list_as, headers: HeaderMap, res: Vec<User> },
+        }
+    );
+
+    http_provider!(
+        UserV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/users", method: GET, fn_name: list, res: Vec<User> },
+        }
+    );
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(header("authorization", "Bearer s3cret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![User { id: 1 }]))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .and(header("authorization", "Bearer s3cret"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(User { id: 2 }))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(header("authorization", "Bearer other"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![User { id: 3 }]))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token_is_sent() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url, Duration::from_secs(5)).with_bearer_token("s3cret");
+
+        assert_eq!(provider.list().await?, vec![User { id: 1 }]);
+        assert_eq!(provider.create(&User { id: 2 }).await?, User { id: 2 });
+
+        // Per-call headers take precedence
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer other".parse()?);
+        assert_eq!(provider.list_as(headers).await?, vec![User { id: 3 }]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token_replaces_url_credentials() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri().replacen("http://", "http://ada:pw@", 1))?;
+        let provider = UserProvider::new(url, Duration::from_secs(5)).with_bearer_token("s3cret");
+
+        assert_eq!(provider.list().await?, vec![User { id: 1 }]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_no_authorization_without_a_token() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(header_exists("authorization"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Vec::<User>::new()))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url, Duration::from_secs(5));
+
+        assert!(provider.list().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_basic_auth_is_sent() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(basic_auth("ada", "pw"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![User { id: 1 }]))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .and(basic_auth("ada", "pw"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(User { id: 2 }))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(header("authorization", "Bearer other"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![User { id: 3 }]))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        // The last credentials set win
+        let provider = UserProvider::new(url, Duration::from_secs(5))
+            .with_bearer_token("s3cret")
+            .with_basic_auth("ada", "pw");
+
+        assert_eq!(provider.list().await?, vec![User { id: 1 }]);
+        assert_eq!(provider.create(&User { id: 2 }).await?, User { id: 2 });
+
+        // Per-call headers take precedence
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer other".parse()?);
+        assert_eq!(provider.list_as(headers).await?, vec![User { id: 3 }]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_output_redacts_credentials() -> Result<(), Box<dyn std::error::Error>> {
+        let url = Url::from_str("https://api.example.com/")?;
+        let provider = UserProvider::new(url, Duration::from_secs(5));
+        let debug = format!("{:?}", provider);
+        assert!(debug.starts_with("UserProvider {"), "{}", debug);
+        assert!(debug.contains("bearer_token: None"), "{}", debug);
+
+        let debug = format!("{:?}", provider.clone().with_bearer_token("s3cret"));
+        assert!(!debug.contains("s3cret"), "{}", debug);
+        assert!(
+            debug.contains(r#"bearer_token: Some("[REDACTED]")"#),
+            "{}",
+            debug
+        );
+
+        let debug = format!("{:?}", provider.with_basic_auth("ada", "pw"));
+        assert!(!debug.contains("pw"), "{}", debug);
+        assert!(
+            debug.contains(r#"basic_auth: Some(("ada", "[REDACTED]"))"#),
+            "{}",
+            debug
+        );
+        assert!(debug.contains("bearer_token: None"), "{}", debug);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_token_is_rejected_redacted() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider =
+            UserV2Provider::new(url, Duration::from_secs(5)).with_bearer_token("s3cret\nx: 1");
+
+        let error = provider.list().await.unwrap_err();
+        match &error {
+            UserV2ProviderError::InvalidHeader {
+                name: "authorization",
+                value: None,
+                ..
+            } => {}
+            error => panic!("unexpected error: {:?}", error),
+        }
+        assert_eq!(
+            error.to_string(),
+            "`authorization` is not a valid header value: [REDACTED]"
+        );
+        assert!(!format!("{:?}", error).contains("s3cret"), "{:?}", error);
+
+        // Nothing was sent without the credentials
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+
+        Ok(())
+    }
+}
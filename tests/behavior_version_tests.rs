@@ -0,0 +1,262 @@
+//! The same endpoints expanded under both behavior versions, pinning what version 2 changes.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Serialize;
+    use serde_json::Value;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{any, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct UserPath {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct Search {
+        q: String,
+    }
+
+    macro_rules! matrix_provider {
+        ($name:ident, $version:tt) => {
+            http_provider!(
+                $name,
+                behavior_version: $version,
+                {
+                    { path: "/users", method: GET, fn_name: list, res: Value },
+                    { path: "users/{id}", method: GET, fn_name: get, path_params: UserPath, res: Value },
+                    { path: "/users", method: POST, fn_name: create, req: Value, res: Value },
+                    { path: "/users/{id}", method: PUT, fn_name: update, path_params: UserPath, req: Value, res: Value },
+                    { path: "/users/{id}", method: DELETE, fn_name: delete, path_params: UserPath, res: Value },
+                    { path: "/search", method: GET, fn_name: search, query_params: Search, res: Value },
+                    { method: GET, fn_name: root, res: Value },
+                    { path: "/count", method: GET, fn_name: count, res: u32 },
+                }
+            );
+        };
+    }
+
+    matrix_provider!(V1Provider, 1);
+    matrix_provider!(V2Provider, 2);
+
+    /// Calls every JSON endpoint of a matrix provider, returning the error messages.
+    macro_rules! call_matrix {
+        ($provider:expr) => {{
+            let provider = &$provider;
+            let user = UserPath { id: 7 };
+            let body = serde_json::json!({ "name": "ada" });
+            let search = Search { q: "ada".to_string() };
+            vec![
+                provider.list().await.map(drop).map_err(|e| e.to_string()),
+                provider.get(&user).await.map(drop).map_err(|e| e.to_string()),
+                provider.create(&body).await.map(drop).map_err(|e| e.to_string()),
+                provider.update(&user, &body).await.map(drop).map_err(|e| e.to_string()),
+                provider.delete(&user).await.map(drop).map_err(|e| e.to_string()),
+                provider.search(&search).await.map(drop).map_err(|e| e.to_string()),
+                provider.root().await.map(drop).map_err(|e| e.to_string()),
+            ]
+        }};
+    }
+
+    async fn mock_server(delay: Duration) -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(path("/count"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("many"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(any())
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({}))
+                    .set_delay(delay),
+            )
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    fn base_url(mock_server: &MockServer) -> Url {
+        Url::from_str(&format!("{}/api", mock_server.uri())).unwrap()
+    }
+
+    async fn requested(mock_server: &MockServer) -> Vec<(String, String, Option<String>)> {
+        mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|request| {
+                let accept = request
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name.as_str().eq_ignore_ascii_case("accept"))
+                    .map(|(_, values)| {
+                        values
+                            .iter()
+                            .map(|value| value.as_str())
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    });
+                (
+                    request.method.to_string(),
+                    request.url.path().to_string(),
+                    accept,
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_version_1_replaces_base_path() {
+        let mock_server = mock_server(Duration::ZERO).await;
+        let provider = V1Provider::new(base_url(&mock_server), Duration::from_secs(5));
+
+        assert!(call_matrix!(provider).iter().all(Result::is_ok));
+
+        // Absolute paths replace `/api`, the relative one replaces its last segment
+        let expected = [
+            ("GET", "/users"),
+            ("GET", "/users/7"),
+            ("POST", "/users"),
+            ("PUT", "/users/7"),
+            ("DELETE", "/users/7"),
+            ("GET", "/search"),
+            ("GET", "/api"),
+        ];
+        let requested = requested(&mock_server).await;
+        assert_eq!(requested.len(), expected.len());
+        for ((method, path, accept), (expected_method, expected_path)) in
+            requested.iter().zip(expected)
+        {
+            assert_eq!(
+                (method.as_str(), path.as_str()),
+                (expected_method, expected_path)
+            );
+            assert_eq!(accept.as_deref(), Some("*/*"), "{}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_version_2_appends_to_base_path() {
+        let mock_server = mock_server(Duration::ZERO).await;
+        let provider = V2Provider::new(base_url(&mock_server), Duration::from_secs(5));
+
+        assert!(call_matrix!(provider).iter().all(Result::is_ok));
+
+        let expected = [
+            ("GET", "/api/users"),
+            ("GET", "/api/users/7"),
+            ("POST", "/api/users"),
+            ("PUT", "/api/users/7"),
+            ("DELETE", "/api/users/7"),
+            ("GET", "/api/search"),
+            ("GET", "/api"),
+        ];
+        let requested = requested(&mock_server).await;
+        assert_eq!(requested.len(), expected.len());
+        for ((method, path, accept), (expected_method, expected_path)) in
+            requested.iter().zip(expected)
+        {
+            assert_eq!(
+                (method.as_str(), path.as_str()),
+                (expected_method, expected_path)
+            );
+            assert_eq!(accept.as_deref(), Some("application/json"), "{}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_is_applied_in_version_2() {
+        let mock_server = mock_server(Duration::from_millis(300)).await;
+        let timeout = Duration::from_millis(50);
+
+        let v1 = V1Provider::new(base_url(&mock_server), timeout);
+        assert!(call_matrix!(v1).iter().all(Result::is_ok));
+
+        let v2 = V2Provider::new(base_url(&mock_server), timeout);
+        for result in call_matrix!(v2) {
+            let error = result.unwrap_err();
+            assert!(error.starts_with("Request failed: "), "{}", error);
+        }
+        match v2.list().await.unwrap_err() {
+            V2ProviderError::Request(source) => assert!(source.is_timeout()),
+            error => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_typed_errors_keep_version_1_messages() {
+        let mock_server = mock_server(Duration::ZERO).await;
+        let root = Url::from_str(&mock_server.uri()).unwrap();
+
+        // Served from `/count` with a body that isn't a number
+        let v1_error = V1Provider::new(root.clone(), Duration::from_secs(5))
+            .count()
+            .await
+            .unwrap_err();
+        let v2_error = V2Provider::new(root, Duration::from_secs(5))
+            .count()
+            .await
+            .unwrap_err();
+        assert!(v1_error.starts_with("Failed to deserialize response: "));
+        assert!(v2_error
+            .to_string()
+            .starts_with("Failed to deserialize response: "));
+        match v2_error {
+            V2ProviderError::NotJson { body_prefix, .. } => assert_eq!(body_prefix, "many"),
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        let unavailable = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(503).set_body_string("maintenance"))
+            .mount(&unavailable)
+            .await;
+        let root = Url::from_str(&unavailable.uri()).unwrap();
+        let v1_error = V1Provider::new(root.clone(), Duration::from_secs(5))
+            .count()
+            .await
+            .unwrap_err();
+        let v2_error = V2Provider::new(root, Duration::from_secs(5))
+            .count()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            v1_error,
+            "HTTP request failed with status 503: Service Unavailable: maintenance"
+        );
+        assert_eq!(v2_error.to_string(), v1_error);
+        match v2_error {
+            V2ProviderError::Status { code, body } => {
+                assert_eq!((code, body.as_str()), (503, "maintenance"))
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_errors_are_typed() {
+        // Nothing listens on the port of a dropped listener
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::from_str(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        drop(listener);
+
+        let v1_error = V1Provider::new(url.clone(), Duration::from_secs(5))
+            .list()
+            .await
+            .unwrap_err();
+        let v2_error = V2Provider::new(url, Duration::from_secs(5))
+            .list()
+            .await
+            .unwrap_err();
+
+        assert_eq!(v2_error.to_string(), v1_error);
+        assert!(matches!(&v2_error, V2ProviderError::Request(source) if source.is_connect()));
+        assert!(std::error::Error::source(&v2_error).is_some());
+    }
+}
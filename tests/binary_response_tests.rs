@@ -0,0 +1,117 @@
+//! `res_format: bytes` endpoints return the response body as received, without deserializing it
+//! and whatever its `Content-Type`.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Serialize;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct ImagePath {
+        id: u32,
+    }
+
+    http_provider!(
+        AssetProvider,
+        behavior_version: 1,
+        {
+            { path: "/images/{id}", method: GET, fn_name: image, path_params: ImagePath, res_format: bytes },
+            { path: "/images/{id}", method: GET, fn_name: image_vec, path_params: ImagePath, res: Vec<u8>, res_format: bytes },
+            { path: "/images/{id}", method: GET, fn_name: find_image, path_params: ImagePath, res_format: bytes, not_found_as_none: true },
+            { path: "/checksums", method: GET, fn_name: checksums, res: Vec<u8> },
+        }
+    );
+
+    http_provider!(
+        AssetV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/archive.zip", method: GET, fn_name: archive, res_format: bytes },
+        }
+    );
+
+    /// A PNG signature followed by bytes that aren't valid UTF-8.
+    const IMAGE: &[u8] = b"\x89PNG\r\n\x1a\n\x00\xff\xfe\xc3\x28";
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/images/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(IMAGE, "image/png"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/images/2"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("no such image"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/checksums"))
+            .respond_with(ResponseTemplate::new(200).set_body_json([1, 2, 3]))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_binary_body_is_returned_as_received() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            AssetProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let image: bytes::Bytes = provider.image(&ImagePath { id: 1 }).await?;
+        assert_eq!(image.as_ref(), IMAGE);
+        let image: Vec<u8> = provider.image_vec(&ImagePath { id: 1 }).await?;
+        assert_eq!(image, IMAGE);
+        assert_eq!(
+            provider.find_image(&ImagePath { id: 1 }).await?.as_deref(),
+            Some(IMAGE)
+        );
+
+        // Error statuses are still errors, and `not_found_as_none` still applies
+        assert_eq!(
+            provider.image(&ImagePath { id: 2 }).await,
+            Err("HTTP request failed with status 404: Not Found: no such image".to_string())
+        );
+        assert_eq!(provider.find_image(&ImagePath { id: 2 }).await?, None);
+
+        // Without `res_format`, a `Vec<u8>` is still deserialized from JSON
+        assert_eq!(provider.checksums().await?, [1, 2, 3]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_large_body_is_not_truncated() -> Result<(), Box<dyn std::error::Error>> {
+        let archive: Vec<u8> = (0..4 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/archive.zip"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(archive.clone(), "application/zip"),
+            )
+            .mount(&mock_server)
+            .await;
+        let provider =
+            AssetV2Provider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(provider.archive().await?.as_ref(), archive.as_slice());
+
+        // Version 2 only asks for JSON on endpoints decoding it
+        let requests = mock_server.received_requests().await.unwrap();
+        let accept = requests[0]
+            .headers
+            .iter()
+            .find(|(name, _)| name.as_str().eq_ignore_ascii_case("accept"))
+            .map(|(_, values)| values.last().as_str().to_string());
+        assert_eq!(accept.as_deref(), Some("*/*"));
+
+        Ok(())
+    }
+}
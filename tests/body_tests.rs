@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_string, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    http_provider!(
+        BodyProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/jobs/run",
+                method: POST,
+                fn_name: run_job,
+                res: Job,
+                empty_json_body: true,
+            },
+        }
+    );
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Job {
+        id: u32,
+    }
+
+    #[tokio::test]
+    async fn test_empty_json_body() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/jobs/run"))
+            .and(header("content-type", "application/json"))
+            .and(body_string("{}"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Job { id: 1 }))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = BodyProvider::new(url, Duration::from_secs(5));
+
+        assert_eq!(provider.run_job().await?, Job { id: 1 });
+
+        Ok(())
+    }
+}
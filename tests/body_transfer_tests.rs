@@ -0,0 +1,111 @@
+//! Under `behavior_version: 2`, a response body cut short of its `Content-Length` is reported as
+//! a `BodyTransfer` error, not as a body that failed to deserialize. The scripted server sends
+//! the truncated body, which wiremock can't.
+mod support;
+
+#[cfg(test)]
+mod tests {
+    use crate::support::{ScriptedResponse, ScriptedServer, Step};
+    use http_provider_macro::http_provider;
+    use serde::{Deserialize, Serialize};
+    use std::error::Error;
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Report {
+        id: u32,
+        rows: Vec<u32>,
+    }
+
+    http_provider!(
+        ReportProvider,
+        behavior_version: 2,
+        {
+            { path: "/report", method: GET, fn_name: report, res: Report },
+            { path: "/report", method: GET, fn_name: report_off_thread, res: Report, deserialize_off_thread: true },
+            { path: "/report", method: GET, fn_name: report_raw, res: Report, keep_raw_body: true },
+            { path: "/report", method: GET, fn_name: report_bytes, res_format: bytes },
+        }
+    );
+
+    /// A response declaring a 100-byte body and closing the connection after `body`.
+    fn truncated(body: &[u8]) -> Vec<Step> {
+        let head = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 100\r\nconnection: close\r\n\r\n";
+        vec![
+            Step::Write(head.as_bytes().to_vec()),
+            Step::Write(body.to_vec()),
+        ]
+    }
+
+    fn assert_truncated(error: &ReportProviderError, bytes_read: u64) {
+        match error {
+            ReportProviderError::BodyTransfer {
+                bytes_read: read,
+                expected,
+                ..
+            } => assert_eq!((*read, *expected), (bytes_read, Some(100))),
+            error => panic!("unexpected error: {:?}", error),
+        }
+        assert!(error.source().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_truncated_body_is_a_transfer_error() {
+        let body = br#"{"id":1,"rows":[1,2,"#;
+        let server = ScriptedServer::start(truncated(body)).await;
+        let provider = ReportProvider::new(server.url(), Duration::from_secs(5));
+        let bytes_read = body.len() as u64;
+
+        let error = provider.report().await.unwrap_err();
+        assert_truncated(&error, bytes_read);
+        assert!(
+            error
+                .to_string()
+                .starts_with("Response body transfer failed after 20 of 100 bytes: "),
+            "{}",
+            error
+        );
+
+        assert_truncated(&provider.report_off_thread().await.unwrap_err(), bytes_read);
+        assert_truncated(&provider.report_raw().await.unwrap_err(), bytes_read);
+        assert_truncated(&provider.report_bytes().await.unwrap_err(), bytes_read);
+    }
+
+    #[tokio::test]
+    async fn test_complete_bodies_are_unaffected() -> Result<(), Box<dyn std::error::Error>> {
+        let report = Report {
+            id: 1,
+            rows: vec![1, 2, 3],
+        };
+        let server =
+            ScriptedServer::start(ScriptedResponse::new(200).json_body(&report).into_script())
+                .await;
+        let provider = ReportProvider::new(server.url(), Duration::from_secs(5));
+
+        assert_eq!(provider.report().await?, report);
+        assert_eq!(provider.report_off_thread().await?, report);
+        let (decoded, raw_body) = provider.report_raw().await?;
+        assert_eq!(decoded, report);
+        assert_eq!(raw_body.as_ref(), serde_json::to_vec(&report)?.as_slice());
+        assert_eq!(
+            provider.report_bytes().await?.as_ref(),
+            serde_json::to_vec(&report)?.as_slice()
+        );
+
+        // A complete body that isn't valid is still a deserialization failure
+        let server = ScriptedServer::start(
+            ScriptedResponse::new(200)
+                .header("content-type", "application/json")
+                .body(r#"{"id":1}"#)
+                .into_script(),
+        )
+        .await;
+        let provider = ReportProvider::new(server.url(), Duration::from_secs(5));
+        assert!(matches!(
+            provider.report().await.unwrap_err(),
+            ReportProviderError::SchemaMismatch { .. }
+        ));
+
+        Ok(())
+    }
+}
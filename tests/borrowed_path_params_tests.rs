@@ -0,0 +1,286 @@
+//! Path parameter structs may borrow their fields, and paths are rendered in a single pass,
+//! without a `String` per placeholder or a rescan of substituted values. Each value is
+//! percent-encoded as one path segment. The counting allocator below only counts the current
+//! thread's allocations, so tests running in parallel don't disturb each other.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // `try_with` keeps allocations made while the thread is torn down working
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the value of `f` with the number of allocations made while running it.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let value = f();
+    (value, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::count_allocations;
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Deserialize;
+    use std::borrow::Cow;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    struct RepoPath<'a> {
+        owner: &'a str,
+        repo: Cow<'a, str>,
+    }
+
+    struct IssuePath<'a> {
+        owner: &'a str,
+        repo: &'a str,
+        number: u64,
+    }
+
+    struct NamedUserPath<'a> {
+        name: &'a str,
+        id: u32,
+    }
+
+    struct SegmentsPath<'a> {
+        a: &'a str,
+        b: &'a str,
+        c: &'a str,
+        d: &'a str,
+        e: &'a str,
+    }
+
+    struct SegmentPath<'a> {
+        a: &'a str,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Repo {
+        name: String,
+    }
+
+    http_provider!(
+        RepoProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/repos/{owner}/{repo}",
+                method: GET,
+                fn_name: repo,
+                path_params: RepoPath<'_>,
+                res: Repo,
+            },
+            {
+                path: "/repos/{owner}/{repo}/issues/{number}",
+                method: GET,
+                fn_name: issue,
+                path_params: IssuePath<'_>,
+                res: Repo,
+            },
+            {
+                path: "/users/{name}/{id}",
+                method: GET,
+                fn_name: named_user,
+                path_params: NamedUserPath<'_>,
+                res: Repo,
+            },
+            {
+                path: "/s/{a}/{b}/{c}/{d}/{e}",
+                method: GET,
+                fn_name: five_segments,
+                path_params: SegmentsPath<'_>,
+                res: Repo,
+            },
+            {
+                path: "/s/{a}/b/c/d/e",
+                method: GET,
+                fn_name: one_segment,
+                path_params: SegmentPath<'_>,
+                res: Repo,
+            },
+        }
+    );
+
+    #[tokio::test]
+    async fn test_borrowed_path_params() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/rust-lang/cargo"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "name": "cargo" })),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/rust-lang/cargo/issues/42"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "name": "issue" })),
+            )
+            .mount(&mock_server)
+            .await;
+        let provider =
+            RepoProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let owners = vec!["rust-lang".to_string()];
+        for owner in &owners {
+            let borrowed = RepoPath {
+                owner,
+                repo: Cow::Borrowed("cargo"),
+            };
+            let owned = RepoPath {
+                owner,
+                repo: Cow::Owned("cargo".to_string()),
+            };
+            let expected = Repo {
+                name: "cargo".to_string(),
+            };
+            assert_eq!(provider.repo(&borrowed).await?, expected);
+            assert_eq!(provider.repo(&owned).await?, expected);
+
+            let issue = IssuePath {
+                owner,
+                repo: "cargo",
+                number: 42,
+            };
+            assert_eq!(provider.issue(&issue).await?.name, "issue");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_values_are_not_substituted_again() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/%7Bid%7D/7"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "name": "ada" })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let provider =
+            RepoProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        // A value looking like a later placeholder is sent as written
+        let user = NamedUserPath {
+            name: "{id}",
+            id: 7,
+        };
+        assert_eq!(provider.named_user(&user).await?.name, "ada");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_values_stay_in_their_segment() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let provider =
+            RepoProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        for (name, requested) in [
+            ("a/b", "/users/a%2Fb/7"),
+            ("a?b=1", "/users/a%3Fb=1/7"),
+            ("../admin", "/users/..%2Fadmin/7"),
+            ("a#b c%", "/users/a%23b%20c%25/7"),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(requested))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(serde_json::json!({ "name": name })),
+                )
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+            let user = NamedUserPath { name, id: 7 };
+            assert_eq!(provider.named_user(&user).await?.name, name);
+            let requests = mock_server.received_requests().await.unwrap();
+            assert_eq!(requests.last().unwrap().url.query(), None);
+        }
+
+        // A segment of `..` can't be sent without being resolved away
+        let user = NamedUserPath { name: "..", id: 7 };
+        assert_eq!(
+            provider.named_user(&user).await.unwrap_err(),
+            "Failed to construct URL: path parameter `name` can't be `..`"
+        );
+
+        Ok(())
+    }
+
+    /// Counts the allocations of a call that is aborted by the request signer, right before
+    /// anything is sent.
+    fn call_allocations<F: std::future::Future>(
+        runtime: &tokio::runtime::Runtime,
+        call: F,
+    ) -> usize {
+        count_allocations(|| runtime.block_on(call)).1
+    }
+
+    #[test]
+    fn test_placeholders_do_not_allocate() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let provider = RepoProvider::new(
+            Url::from_str("http://localhost:1").unwrap(),
+            Duration::from_secs(5),
+        )
+        .with_request_signer(|_| Err(String::new()));
+        let five = SegmentsPath {
+            a: "a",
+            b: "b",
+            c: "c",
+            d: "d",
+            e: "e",
+        };
+        let one = SegmentPath { a: "a" };
+
+        // Warm up lazily initialized state, then measure both endpoints rendering the same path
+        let _ = call_allocations(&runtime, provider.five_segments(&five));
+        let _ = call_allocations(&runtime, provider.one_segment(&one));
+        let five_allocations = call_allocations(&runtime, provider.five_segments(&five));
+        let one_allocations = call_allocations(&runtime, provider.one_segment(&one));
+
+        // Substituting with `to_string` and `str::replace` costs two allocations per placeholder
+        let (replaced, replace_allocations) = count_allocations(|| {
+            let mut path = "/s/{a}/{b}/{c}/{d}/{e}".to_string();
+            let values: [(&str, &dyn std::fmt::Display); 5] = [
+                ("a", &five.a),
+                ("b", &five.b),
+                ("c", &five.c),
+                ("d", &five.d),
+                ("e", &five.e),
+            ];
+            for (name, value) in values {
+                path = path.replace(&format!("{{{}}}", name), &value.to_string());
+            }
+            path
+        });
+        assert_eq!(replaced, "/s/a/b/c/d/e");
+
+        assert_eq!(five_allocations, one_allocations);
+        assert!(replace_allocations >= 10, "{}", replace_allocations);
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    http_provider!(
+        ItemsProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/items",
+                method: GET,
+                fn_name: list_items,
+                res: Vec<u32>,
+            },
+        }
+    );
+
+    /// Starts a keep-alive server answering every request with `[]`, returning its base URL
+    /// and the number of connections accepted so far.
+    async fn counting_server() -> (reqwest::Url, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        tokio::spawn({
+            let connections = connections.clone();
+            async move {
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    connections.fetch_add(1, Ordering::SeqCst);
+                    tokio::spawn(async move {
+                        let mut buffer = Vec::new();
+                        let mut chunk = [0; 1024];
+                        loop {
+                            let Ok(read) = stream.read(&mut chunk).await else {
+                                return;
+                            };
+                            if read == 0 {
+                                return;
+                            }
+                            buffer.extend_from_slice(&chunk[..read]);
+                            // GET requests have no body, so each blank line ends a request
+                            while let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                                buffer.drain(..end + 4);
+                                let response =
+                                    b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 2\r\n\r\n[]";
+                                if stream.write_all(response).await.is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                    });
+                }
+            }
+        });
+
+        (url, connections)
+    }
+
+    async fn call_three_times(provider: &ItemsProvider) {
+        for _ in 0..3 {
+            assert_eq!(provider.list_items().await.unwrap(), Vec::<u32>::new());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_builder_reuses_connections() {
+        let (url, connections) = counting_server().await;
+        let provider = ItemsProvider::builder(url).build().unwrap();
+
+        call_three_times(&provider).await;
+
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fresh_connections() {
+        let (url, connections) = counting_server().await;
+        let provider = ItemsProvider::builder(url)
+            .fresh_connections(true)
+            .build()
+            .unwrap();
+
+        call_three_times(&provider).await;
+
+        assert_eq!(connections.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_pool_and_nodelay_options() {
+        let (url, connections) = counting_server().await;
+        let provider = ItemsProvider::builder(url)
+            .timeout(Duration::from_secs(2))
+            .pool_max_idle_per_host(0)
+            .tcp_nodelay(true)
+            .build()
+            .unwrap();
+
+        call_three_times(&provider).await;
+
+        assert_eq!(connections.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,108 @@
+//! `req_format: bytes` endpoints send their body as given, as `application/octet-stream` by
+//! default.
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, Url};
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_bytes, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Receipt {
+        size: usize,
+    }
+
+    #[derive(Serialize)]
+    struct BlobPath {
+        id: u32,
+    }
+
+    http_provider!(
+        BlobProvider,
+        behavior_version: 1,
+        {
+            { path: "/blobs/{id}", method: PUT, fn_name: put_blob, path_params: BlobPath, req_format: bytes, res: Receipt, retries: 1 },
+            { path: "/images", method: POST, fn_name: upload_image, req: Vec<u8>, req_format: bytes, content_type: "image/png", headers: HeaderMap, res: Receipt },
+            { path: "/notes", method: POST, fn_name: upload_note, req: String, req_format: bytes, res: Receipt, args_struct: true },
+        }
+    );
+
+    fn receipt(size: usize) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "size": size }))
+    }
+
+    #[tokio::test]
+    async fn test_body_is_sent_unchanged() -> Result<(), Box<dyn std::error::Error>> {
+        let blob: &[u8] = &[0, 159, 146, 150, b'{'];
+        let mock_server = MockServer::start().await;
+        // The first attempt fails, so the retry resends the same bytes
+        Mock::given(method("PUT"))
+            .and(path("/blobs/1"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/blobs/1"))
+            .and(header("content-type", "application/octet-stream"))
+            .and(body_bytes(blob))
+            .respond_with(receipt(blob.len()))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/images"))
+            .and(header("content-type", "image/png"))
+            .and(body_bytes(blob))
+            .respond_with(receipt(blob.len()))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/images"))
+            .and(header("content-type", "image/webp"))
+            .respond_with(receipt(0))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/notes"))
+            .and(header("content-type", "application/octet-stream"))
+            .and(body_bytes("plain text"))
+            .respond_with(receipt(10))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = BlobProvider::new(url, Duration::from_secs(5));
+
+        let sent = provider
+            .put_blob(&BlobPath { id: 1 }, Bytes::from_static(blob))
+            .await?;
+        assert_eq!(sent, Receipt { size: 5 });
+
+        let sent = provider
+            .upload_image(blob.to_vec(), HeaderMap::new())
+            .await?;
+        assert_eq!(sent, Receipt { size: 5 });
+
+        // A `Content-Type` passed per call still wins
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", "image/webp".parse()?);
+        assert_eq!(
+            provider.upload_image(blob.to_vec(), headers).await?,
+            Receipt { size: 0 }
+        );
+
+        let sent = provider
+            .upload_note(BlobProviderUploadNoteArgs {
+                body: "plain text".to_string(),
+            })
+            .await?;
+        assert_eq!(sent, Receipt { size: 10 });
+
+        Ok(())
+    }
+}
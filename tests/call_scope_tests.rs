@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, Url};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    /// A trait whose signature can't take per-call options.
+    #[async_trait]
+    trait Inventory {
+        async fn stock(&self) -> Result<u32, String>;
+    }
+
+    http_provider!(
+        InventoryProvider,
+        behavior_version: 1,
+        call_scope: true,
+        {
+            {
+                path: "/stock",
+                method: GET,
+                fn_name: stock,
+                res: u32,
+                trait_impl: Inventory,
+                trait_style: async_trait,
+            },
+            {
+                path: "/slow",
+                method: GET,
+                fn_name: slow,
+                res: u32,
+            },
+            {
+                path: "/tagged",
+                method: GET,
+                fn_name: tagged,
+                res: u32,
+                headers: HeaderMap,
+            },
+        }
+    );
+
+    http_provider!(
+        DeadlineInventoryProvider,
+        behavior_version: 1,
+        call_scope: true,
+        deadline_header: "x-deadline-ms",
+        {
+            {
+                path: "/stock",
+                method: GET,
+                fn_name: stock,
+                res: u32,
+                trait_impl: Inventory,
+                trait_style: async_trait,
+            },
+        }
+    );
+
+    fn tenant(value: &str) -> InventoryProviderCallOptions {
+        let mut options = InventoryProviderCallOptions::default();
+        options.headers.insert("x-tenant", value.parse().unwrap());
+        options
+    }
+
+    fn tenants(request: &wiremock::Request) -> Vec<String> {
+        request
+            .headers
+            .iter()
+            .filter(|(name, _)| name.as_str().eq_ignore_ascii_case("x-tenant"))
+            .flat_map(|(_, values)| values.iter().map(|value| value.as_str().to_string()))
+            .collect()
+    }
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/stock"))
+            .and(header("x-tenant", "acme"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(7))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/stock"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(0))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(1)
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/tagged"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(2))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_scope_applies_header_to_trait_method() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            InventoryProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let scoped = provider
+            .with_scope(tenant("acme"), Inventory::stock(&provider))
+            .await?;
+        let unscoped = Inventory::stock(&provider).await?;
+
+        assert_eq!((scoped, unscoped), (7, 0));
+
+        let received = mock_server.received_requests().await.unwrap();
+        assert_eq!(tenants(&received[0]), ["acme"]);
+        assert!(tenants(&received[1]).is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_inner_scope_replaces_outer() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            InventoryProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let stock = provider
+            .with_scope(tenant("other"), async {
+                provider
+                    .with_scope(tenant("acme"), Inventory::stock(&provider))
+                    .await
+            })
+            .await?;
+
+        assert_eq!(stock, 7);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scope_timeout() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            InventoryProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let options = InventoryProviderCallOptions {
+            timeout: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+        let error = provider
+            .with_scope(options, provider.slow())
+            .await
+            .unwrap_err();
+        assert!(error.contains("timed out"), "{}", error);
+
+        assert_eq!(provider.slow().await?, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scope_timeout_is_the_advertised_deadline(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider = DeadlineInventoryProvider::new(
+            Url::from_str(&mock_server.uri())?,
+            Duration::from_secs(5),
+        );
+
+        let options = |millis| DeadlineInventoryProviderCallOptions {
+            timeout: Some(Duration::from_millis(millis)),
+            ..Default::default()
+        };
+        provider
+            .with_scope(options(3000), async {
+                provider
+                    .with_scope(options(1000), Inventory::stock(&provider))
+                    .await?;
+                Inventory::stock(&provider).await
+            })
+            .await?;
+        Inventory::stock(&provider).await?;
+
+        let received = mock_server.received_requests().await.unwrap();
+        let deadlines: Vec<&str> = received
+            .iter()
+            .map(|request| {
+                request
+                    .headers
+                    .get(&"x-deadline-ms".into())
+                    .unwrap()
+                    .as_str()
+            })
+            .collect();
+        assert_eq!(deadlines, ["1000", "3000", "5000"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_call_headers_override_scope() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            InventoryProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-tenant", "explicit".parse()?);
+        provider
+            .with_scope(tenant("acme"), provider.tagged(headers))
+            .await?;
+
+        let received = mock_server.received_requests().await.unwrap();
+        assert_eq!(tenants(&received[0]), ["explicit"]);
+
+        Ok(())
+    }
+}
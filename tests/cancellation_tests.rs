@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use tokio::task::JoinSet;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Item {
+        id: u32,
+    }
+
+    http_provider!(
+        CancellationProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/slow",
+                method: GET,
+                fn_name: get_slow,
+                res: Item,
+            },
+            {
+                path: "/fast",
+                method: GET,
+                fn_name: get_fast,
+                res: Item,
+            },
+            {
+                path: "/missing",
+                method: GET,
+                fn_name: get_missing,
+                res: Item,
+            },
+        }
+    );
+
+    async fn mount(mock_server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(Item { id: 1 })
+                    .set_delay(Duration::from_secs(10)),
+            )
+            .mount(mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/fast"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Item { id: 2 }))
+            .mount(mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("no such item"))
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_calls_do_not_exhaust_pool() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+
+        let provider = Arc::new(CancellationProvider::new(
+            Url::from_str(&mock_server.uri())?,
+            Duration::from_secs(5),
+        ));
+
+        // Abandon many concurrent in-flight calls, as a losing `select!` branch would
+        let mut cancelled = JoinSet::new();
+        for _ in 0..100 {
+            let provider = provider.clone();
+            cancelled.spawn(async move {
+                tokio::time::timeout(Duration::from_millis(20), provider.get_slow()).await
+            });
+        }
+        while let Some(result) = cancelled.join_next().await {
+            assert!(result?.is_err(), "slow call should have been cancelled");
+        }
+
+        let started = Instant::now();
+        for _ in 0..50 {
+            assert_eq!(provider.get_fast().await?, Item { id: 2 });
+        }
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error_responses_do_not_exhaust_pool() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+
+        let provider =
+            CancellationProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let started = Instant::now();
+        for _ in 0..50 {
+            assert_eq!(
+                provider.get_missing().await,
+                Err("HTTP request failed with status 404: Not Found: no such item".to_string())
+            );
+            assert_eq!(provider.get_fast().await?, Item { id: 2 });
+        }
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_string, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct Transfer {
+        to: String,
+        amount: u64,
+        memo: Memo,
+    }
+
+    // Same fields as `Transfer`, declared in a different order
+    #[derive(Serialize)]
+    struct ReorderedTransfer {
+        memo: Memo,
+        amount: u64,
+        to: String,
+    }
+
+    #[derive(Serialize)]
+    struct Memo {
+        text: String,
+        tags: Vec<String>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Receipt {
+        id: u32,
+    }
+
+    http_provider!(
+        PartnerProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/transfers",
+                method: POST,
+                fn_name: create_transfer,
+                req: Transfer,
+                res: Receipt,
+                canonical_json: true,
+            },
+            {
+                path: "/transfers",
+                method: POST,
+                fn_name: create_reordered_transfer,
+                req: ReorderedTransfer,
+                res: Receipt,
+                canonical_json: true,
+            },
+        }
+    );
+
+    const CANONICAL: &str =
+        r#"{"amount":250,"memo":{"tags":["rent","q3"],"text":"rent, q3"},"to":"acct-1"}"#;
+
+    fn memo() -> Memo {
+        Memo {
+            text: "rent, q3".to_string(),
+            tags: vec!["rent".to_string(), "q3".to_string()],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_body_is_canonical() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/transfers"))
+            .and(header("content-type", "application/json"))
+            .and(body_string(CANONICAL))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"id":1}"#))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            PartnerProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let transfer = Transfer {
+            to: "acct-1".to_string(),
+            amount: 250,
+            memo: memo(),
+        };
+        assert_eq!(
+            provider.create_transfer(&transfer).await?,
+            Receipt { id: 1 }
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signed_bytes_ignore_field_order() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/transfers"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"id":1}"#))
+            .mount(&mock_server)
+            .await;
+
+        let signed = Arc::new(Mutex::new(Vec::new()));
+        let sink = signed.clone();
+        let provider =
+            PartnerProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_request_signer(move |request| {
+                    let body = request
+                        .body()
+                        .and_then(|body| body.as_bytes())
+                        .unwrap_or_default();
+                    sink.lock()
+                        .unwrap()
+                        .push(String::from_utf8_lossy(body).into_owned());
+                    Ok(())
+                });
+
+        provider
+            .create_transfer(&Transfer {
+                to: "acct-1".to_string(),
+                amount: 250,
+                memo: memo(),
+            })
+            .await?;
+        provider
+            .create_reordered_transfer(&ReorderedTransfer {
+                memo: memo(),
+                amount: 250,
+                to: "acct-1".to_string(),
+            })
+            .await?;
+
+        assert_eq!(*signed.lock().unwrap(), vec![CANONICAL, CANONICAL]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+//! `codec: cbor` endpoints send and decode CBOR bodies. Run with
+//! `cargo test --features cbor`.
+#![cfg(feature = "cbor")]
+
+mod support;
+
+#[cfg(test)]
+mod tests {
+    crate::codec_tests!(cbor, "application/cbor", {
+        let mut encoded = Vec::new();
+        ciborium::ser::into_writer(&sample(), &mut encoded)?;
+        encoded
+    });
+}
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, Url};
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+    #[derive(Serialize)]
+    struct Upload {
+        name: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Ack {}
+
+    // One endpoint per body mode × `content_type` option
+    http_provider!(
+        UploadProvider,
+        behavior_version: 1,
+        {
+            { path: "/none/default", method: POST, fn_name: none_default, res: Ack },
+            { path: "/none/custom", method: POST, fn_name: none_custom, res: Ack, content_type: "text/plain" },
+            { path: "/json/default", method: POST, fn_name: json_default, req: Upload, res: Ack },
+            { path: "/json/custom", method: POST, fn_name: json_custom, req: Upload, res: Ack, content_type: "application/vnd.api+json" },
+            { path: "/json/omit", method: POST, fn_name: json_omit, req: Upload, res: Ack, content_type: none },
+            { path: "/canonical/default", method: POST, fn_name: canonical_default, req: Upload, res: Ack, canonical_json: true },
+            { path: "/canonical/custom", method: POST, fn_name: canonical_custom, req: Upload, res: Ack, canonical_json: true, content_type: "text/plain" },
+            { path: "/canonical/omit", method: POST, fn_name: canonical_omit, req: Upload, res: Ack, canonical_json: true, content_type: none },
+            { path: "/empty/default", method: POST, fn_name: empty_default, res: Ack, empty_json_body: true },
+            { path: "/empty/custom", method: POST, fn_name: empty_custom, res: Ack, empty_json_body: true, content_type: "text/plain" },
+            { path: "/empty/omit", method: POST, fn_name: empty_omit, res: Ack, empty_json_body: true, content_type: none },
+            { path: "/override", method: POST, fn_name: json_override, req: Upload, res: Ack, headers: HeaderMap, content_type: "text/plain" },
+        }
+    );
+
+    fn upload() -> Upload {
+        Upload {
+            name: "report".to_string(),
+        }
+    }
+
+    fn content_types(request: &wiremock::Request) -> Vec<String> {
+        request
+            .headers
+            .iter()
+            .filter(|(name, _)| name.as_str().eq_ignore_ascii_case("content-type"))
+            .flat_map(|(_, values)| values.iter().map(|value| value.as_str().to_string()))
+            .collect()
+    }
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"ok":true}"#))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_content_type_per_body_mode() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            UploadProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        provider.none_default().await?;
+        provider.none_custom().await?;
+        provider.json_default(&upload()).await?;
+        provider.json_custom(&upload()).await?;
+        provider.json_omit(&upload()).await?;
+        provider.canonical_default(&upload()).await?;
+        provider.canonical_custom(&upload()).await?;
+        provider.canonical_omit(&upload()).await?;
+        provider.empty_default().await?;
+        provider.empty_custom().await?;
+        provider.empty_omit().await?;
+
+        // (path, expected Content-Type, expected body)
+        let expected = [
+            ("/none/default", None, ""),
+            ("/none/custom", Some("text/plain"), ""),
+            (
+                "/json/default",
+                Some("application/json"),
+                r#"{"name":"report"}"#,
+            ),
+            (
+                "/json/custom",
+                Some("application/vnd.api+json"),
+                r#"{"name":"report"}"#,
+            ),
+            ("/json/omit", None, r#"{"name":"report"}"#),
+            (
+                "/canonical/default",
+                Some("application/json"),
+                r#"{"name":"report"}"#,
+            ),
+            (
+                "/canonical/custom",
+                Some("text/plain"),
+                r#"{"name":"report"}"#,
+            ),
+            ("/canonical/omit", None, r#"{"name":"report"}"#),
+            ("/empty/default", Some("application/json"), "{}"),
+            ("/empty/custom", Some("text/plain"), "{}"),
+            ("/empty/omit", None, "{}"),
+        ];
+
+        let received = mock_server.received_requests().await.unwrap();
+        assert_eq!(received.len(), expected.len());
+        for (request, (path, content_type, body)) in received.iter().zip(expected) {
+            assert_eq!(request.url.path(), path);
+            let expected: Vec<_> = content_type.into_iter().collect();
+            assert_eq!(content_types(request), expected, "{}", path);
+            assert_eq!(String::from_utf8_lossy(&request.body), body, "{}", path);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_per_call_header_overrides_content_type() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = mock_server().await;
+        let provider =
+            UploadProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", "application/x-custom".parse()?);
+        provider.json_override(&upload(), headers).await?;
+
+        let received = mock_server.received_requests().await.unwrap();
+        assert_eq!(content_types(&received[0]), ["application/x-custom"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_content_type_replaces_header_context() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            UploadProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_header_context(|| {
+                    let mut headers = HeaderMap::new();
+                    headers.insert("content-type", "text/html".parse().unwrap());
+                    Some(headers)
+                });
+
+        provider.json_default(&upload()).await?;
+
+        let received = mock_server.received_requests().await.unwrap();
+        assert_eq!(content_types(&received[0]), ["application/json"]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    http_provider!(
+        DeadlineProvider,
+        behavior_version: 1,
+        deadline_header: "x-deadline-ms",
+        {
+            {
+                path: "/work",
+                method: GET,
+                fn_name: work,
+                res: String,
+            },
+        }
+    );
+
+    http_provider!(
+        PlainProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/work",
+                method: GET,
+                fn_name: work,
+                res: String,
+            },
+        }
+    );
+
+    http_provider!(
+        ScopedDeadlineProvider,
+        behavior_version: 1,
+        call_scope: true,
+        deadline_header: "x-deadline-ms",
+        {
+            {
+                path: "/work",
+                method: GET,
+                fn_name: work,
+                res: String,
+            },
+        }
+    );
+
+    fn deadline(request: &wiremock::Request) -> u64 {
+        request
+            .headers
+            .get(&"x-deadline-ms".into())
+            .expect("deadline header must be sent")
+            .as_str()
+            .parse()
+            .unwrap()
+    }
+
+    async fn mock_server(delay: Duration) -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/work"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json("done")
+                    .set_delay(delay),
+            )
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_sends_provider_timeout() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server(Duration::ZERO).await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = DeadlineProvider::new(url, Duration::from_millis(2500));
+
+        provider.work().await?;
+
+        let received = mock_server.received_requests().await.unwrap();
+        let deadline = deadline(&received[0]);
+        assert!((2400..=2500).contains(&deadline), "{}", deadline);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_enforces_advertised_deadline() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server(Duration::from_secs(2)).await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = DeadlineProvider::new(url, Duration::from_millis(200));
+
+        let error = provider.work().await.unwrap_err();
+        assert!(error.contains("timed out"), "{}", error);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sends_scoped_timeout() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server(Duration::from_millis(500)).await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ScopedDeadlineProvider::new(url, Duration::from_secs(5));
+
+        let options = ScopedDeadlineProviderCallOptions {
+            timeout: Some(Duration::from_millis(200)),
+            ..Default::default()
+        };
+        let error = provider
+            .with_scope(options, provider.work())
+            .await
+            .unwrap_err();
+        assert!(error.contains("timed out"), "{}", error);
+
+        // Without a scope the provider's timeout is advertised again
+        provider.work().await?;
+
+        let received = mock_server.received_requests().await.unwrap();
+        assert_eq!(deadline(&received[0]), 200);
+        assert_eq!(deadline(&received[1]), 5000);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_absent_without_option() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server(Duration::ZERO).await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = PlainProvider::new(url, Duration::from_millis(2500));
+
+        provider.work().await?;
+
+        let received = mock_server.received_requests().await.unwrap();
+        assert!(!received[0].headers.contains_key(&"x-deadline-ms".into()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+//! Under `behavior_version: 2`, a body that isn't JSON at all fails with `NotJson` and a JSON
+//! body of the wrong shape with `SchemaMismatch`. The path of the mismatch is only reported
+//! with `cargo test --features json-path`.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        id: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Page {
+        items: Vec<Item>,
+    }
+
+    http_provider!(
+        CatalogProvider,
+        behavior_version: 2,
+        {
+            { path: "/items", method: GET, fn_name: list, res: Page },
+            { path: "/items", method: GET, fn_name: list_off_thread, res: Page, deserialize_off_thread: true },
+        }
+    );
+
+    const PROXY_PAGE: &str = "<html><body><h1>502 Bad Gateway</h1></body></html>";
+
+    async fn provider(response: ResponseTemplate) -> (MockServer, CatalogProvider) {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri()).unwrap();
+        let provider = CatalogProvider::new(url, Duration::from_secs(5));
+        (mock_server, provider)
+    }
+
+    #[tokio::test]
+    async fn test_html_page_is_not_json() -> Result<(), Box<dyn std::error::Error>> {
+        let (_mock_server, provider) =
+            provider(ResponseTemplate::new(200).set_body_raw(PROXY_PAGE.repeat(20), "text/html"))
+                .await;
+
+        for error in [
+            provider.list().await.unwrap_err(),
+            provider.list_off_thread().await.unwrap_err(),
+        ] {
+            assert!(error
+                .to_string()
+                .starts_with("Failed to deserialize response: "));
+            match error {
+                CatalogProviderError::NotJson {
+                    content_type,
+                    body_prefix,
+                    ..
+                } => {
+                    assert_eq!(content_type.as_deref(), Some("text/html"));
+                    assert_eq!(body_prefix.len(), 512);
+                    assert!(body_prefix.starts_with(PROXY_PAGE));
+                }
+                error => panic!("unexpected error: {:?}", error),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wrong_shape_is_a_schema_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+        let body = r#"{"items":[{"id":1},{"id":"two"}]}"#;
+        let (_mock_server, provider) =
+            provider(ResponseTemplate::new(200).set_body_raw(body, "application/json")).await;
+
+        match provider.list().await.unwrap_err() {
+            CatalogProviderError::SchemaMismatch {
+                serde_error,
+                json_path,
+                body: received,
+                ..
+            } => {
+                assert!(serde_error.is_data(), "{}", serde_error);
+                assert_eq!(received, body);
+                if cfg!(feature = "json-path") {
+                    assert_eq!(json_path.as_deref(), Some("items[1].id"));
+                } else {
+                    assert_eq!(json_path, None);
+                }
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        Ok(())
+    }
+}
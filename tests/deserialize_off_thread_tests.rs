@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Deserializer};
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+    use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+    http_provider!(
+        ReportProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/report",
+                method: GET,
+                fn_name: report_on_runtime,
+                res: Vec<Row>,
+            },
+            {
+                path: "/report",
+                method: GET,
+                fn_name: report,
+                res: Vec<Row>,
+                deserialize_off_thread: true,
+            },
+            {
+                path: "/report",
+                method: GET,
+                fn_name: report_with_body,
+                res: Vec<Row>,
+                deserialize_off_thread: true,
+                keep_raw_body: true,
+            },
+            {
+                path: "/report",
+                method: GET,
+                fn_name: explode,
+                res: Explodes,
+                deserialize_off_thread: true,
+            },
+        }
+    );
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Row {
+        id: u64,
+        label: String,
+        values: Vec<f64>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Explodes {
+        #[serde(deserialize_with = "explode")]
+        _first: (),
+    }
+
+    fn explode<'de, D: Deserializer<'de>>(_: D) -> Result<(), D::Error> {
+        panic!("malformed report");
+    }
+
+    /// A JSON array of `rows` rows, a few megabytes for 20 000 rows.
+    fn report_body(rows: u64) -> String {
+        let rows: Vec<String> = (0..rows)
+            .map(|id| {
+                format!(
+                    r#"{{"id":{},"label":"row {}","values":[1.5,2.5,3.5,4.5,5.5,6.5,7.5,8.5]}}"#,
+                    id, id
+                )
+            })
+            .collect();
+        format!("[{}]", rows.join(","))
+    }
+
+    async fn provider(
+        body: String,
+    ) -> Result<(MockServer, ReportProvider), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ReportProvider::new(url, Duration::from_secs(30));
+        Ok((mock_server, provider))
+    }
+
+    /// Runs `call` next to a heartbeat task ticking every millisecond on the same
+    /// single-threaded runtime, returning the longest gap between two ticks.
+    async fn longest_heartbeat_gap(call: impl std::future::Future<Output = ()>) -> Duration {
+        let longest = Arc::new(Mutex::new(Duration::ZERO));
+        let heartbeat = tokio::spawn({
+            let longest = longest.clone();
+            async move {
+                let mut last = Instant::now();
+                loop {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                    let mut longest = longest.lock().unwrap();
+                    *longest = (*longest).max(last.elapsed());
+                    last = Instant::now();
+                }
+            }
+        });
+        tokio::task::yield_now().await;
+
+        call.await;
+        // Let the heartbeat record the gap a blocked runtime left before stopping it
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        heartbeat.abort();
+
+        let longest = *longest.lock().unwrap();
+        longest
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_runtime_stays_responsive() -> Result<(), Box<dyn std::error::Error>> {
+        let (_mock_server, provider) = provider(report_body(50_000)).await?;
+
+        let on_runtime = longest_heartbeat_gap(async {
+            assert_eq!(provider.report_on_runtime().await.unwrap().len(), 50_000);
+        })
+        .await;
+        let off_thread = longest_heartbeat_gap(async {
+            assert_eq!(provider.report().await.unwrap().len(), 50_000);
+        })
+        .await;
+
+        // Parsing on the runtime thread stalls the heartbeat for the whole parse
+        assert!(
+            off_thread < on_runtime / 2,
+            "longest heartbeat gap: {:?} on the runtime, {:?} off thread",
+            on_runtime,
+            off_thread
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_keeps_raw_body() -> Result<(), Box<dyn std::error::Error>> {
+        let body = report_body(3);
+        let (_mock_server, provider) = provider(body.clone()).await?;
+
+        let (rows, raw_body) = provider.report_with_body().await?;
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2].label, "row 2");
+        assert_eq!(raw_body, body.as_bytes());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_panic_becomes_error() -> Result<(), Box<dyn std::error::Error>> {
+        let (_mock_server, provider) = provider(report_body(1)).await?;
+
+        let error = provider.explode().await.unwrap_err();
+        assert_eq!(error, "Response deserialization panicked: malformed report");
+
+        // The provider is still usable after the panic
+        assert_eq!(provider.report().await?.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+//! `download` endpoints write the response body into a caller-provided `AsyncWrite` as it
+//! arrives, returning the number of bytes written. The scripted server cuts a transfer short,
+//! which wiremock can't.
+mod support;
+
+#[cfg(test)]
+mod tests {
+    use crate::support::{split_every, ScriptedResponse, ScriptedServer};
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Serialize;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct ArtifactPath {
+        name: String,
+    }
+
+    http_provider!(
+        ArtifactProvider,
+        behavior_version: 1,
+        {
+            { path: "/artifacts/{name}", method: GET, fn_name: download, path_params: ArtifactPath, download: true },
+            { path: "/artifacts/{name}", method: GET, fn_name: download_args, path_params: ArtifactPath, download: true, args_struct: true },
+            { path: "/links/{name}", method: GET, fn_name: download_presigned, path_params: ArtifactPath, download: true, follow_presigned: true },
+        }
+    );
+
+    http_provider!(
+        ArtifactV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/artifact", method: GET, fn_name: download, download: true },
+        }
+    );
+
+    fn artifact() -> Vec<u8> {
+        (0..100_000u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn artifact_path(name: &str) -> ArtifactPath {
+        ArtifactPath {
+            name: name.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_body_is_written_to_the_writer() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/artifacts/build.tar"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(artifact()))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/links/build.tar"))
+            .respond_with(ResponseTemplate::new(302).insert_header(
+                "location",
+                format!("{}/artifacts/build.tar", mock_server.uri()).as_str(),
+            ))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ArtifactProvider::new(url, Duration::from_secs(5));
+
+        let mut written = Vec::new();
+        let count = provider
+            .download(&artifact_path("build.tar"), &mut written)
+            .await?;
+        assert_eq!(count, artifact().len() as u64);
+        assert_eq!(written, artifact());
+
+        let mut written = Vec::new();
+        let count = provider
+            .download_args(
+                ArtifactProviderDownloadArgsArgs {
+                    path: artifact_path("build.tar"),
+                },
+                &mut written,
+            )
+            .await?;
+        assert_eq!(count, artifact().len() as u64);
+
+        let mut written = Vec::new();
+        provider
+            .download_presigned(&artifact_path("build.tar"), &mut written)
+            .await?;
+        assert_eq!(written, artifact());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error_statuses_write_nothing() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("no such artifact"))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ArtifactProvider::new(url, Duration::from_secs(5));
+
+        let mut written = Vec::new();
+        let error = provider
+            .download(&artifact_path("missing.tar"), &mut written)
+            .await
+            .unwrap_err();
+        assert!(error.contains("404"), "{}", error);
+        assert!(written.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_transfer_keeps_the_partial_write() {
+        let body = artifact();
+        let script = ScriptedResponse::new(200)
+            .chunked_body(split_every(&body, 1000))
+            .reset_after(3000)
+            .into_script();
+        let server = ScriptedServer::start(script).await;
+        let provider = ArtifactV2Provider::new(server.url(), Duration::from_secs(5));
+
+        let mut written = Vec::new();
+        match provider.download(&mut written).await.unwrap_err() {
+            ArtifactV2ProviderError::BodyTransfer { bytes_read, .. } => {
+                assert_eq!(bytes_read, written.len() as u64)
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+        assert!(written.len() >= 2000, "{}", written.len());
+        assert_eq!(written, body[..written.len()]);
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Deserialize;
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct Order {
+        id: u32,
+    }
+
+    http_provider!(
+        OrderProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/orders/{id}",
+                method: GET,
+                fn_name: get_order,
+                path_params: OrderPath,
+                res: Order,
+                emit: Outbox,
+            },
+            {
+                path: "/audited",
+                method: GET,
+                fn_name: get_audited,
+                res: Order,
+                emit: PanickingSink,
+            },
+        }
+    );
+
+    #[derive(serde::Serialize)]
+    struct OrderPath {
+        id: u32,
+    }
+
+    /// Records every accepted event, shared with the test through `events`.
+    #[derive(Clone, Default)]
+    struct Outbox {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl OrderProviderResponseSink<Order> for Outbox {
+        fn accept(&self, response: &Order) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("order-fetched:{}", response.id));
+        }
+    }
+
+    struct PanickingSink;
+
+    impl OrderProviderResponseSink<Order> for PanickingSink {
+        fn accept(&self, _response: &Order) {
+            panic!("sink failure");
+        }
+    }
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/orders/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 7 })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orders/8"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/audited"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 1 })))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_sink_receives_response_before_return() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let outbox = Outbox::default();
+        let provider =
+            OrderProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_sink(outbox.clone());
+
+        let order = provider.get_order(&OrderPath { id: 7 }).await?;
+        // The event is recorded by the time the call returns
+        outbox.events.lock().unwrap().push("returned".to_string());
+        assert_eq!(order, Order { id: 7 });
+
+        // Failed calls emit nothing
+        assert!(provider.get_order(&OrderPath { id: 8 }).await.is_err());
+
+        assert_eq!(
+            *outbox.events.lock().unwrap(),
+            ["order-fetched:7", "returned"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_panicking_sink_keeps_result() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            OrderProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_sink(PanickingSink);
+
+        assert_eq!(provider.get_audited().await?, Order { id: 1 });
+        assert_eq!(provider.get_audited().await?, Order { id: 1 });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_sink_is_skipped() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            OrderProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(
+            provider.get_order(&OrderPath { id: 7 }).await?,
+            Order { id: 7 }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use garden::api::primitives::{Response, Status};
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Order {
+        id: u32,
+    }
+
+    http_provider!(
+        EnvelopeProvider,
+        behavior_version: 1,
+        envelope: { status_field: status, ok_value: "Ok", error_field: error, data_field: result },
+        {
+            {
+                path: "/orders",
+                method: GET,
+                fn_name: get_order,
+                res: Order,
+            },
+        }
+    );
+
+    http_provider!(
+        EndpointEnvelopeProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/orders",
+                method: GET,
+                fn_name: get_order,
+                res: Order,
+                envelope: { status_field: status, ok_value: "Ok", error_field: error, data_field: result },
+            },
+            {
+                path: "/orders",
+                method: GET,
+                fn_name: get_raw_order,
+                res: Response<Order>,
+            },
+        }
+    );
+
+    async fn mount_response(mock_server: &MockServer, response: Response<Order>) {
+        Mock::given(method("GET"))
+            .and(path("/orders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(response))
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_ok_envelope_is_unwrapped() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount_response(
+            &mock_server,
+            Response {
+                status: Status::Ok,
+                result: Some(Order { id: 3 }),
+                error: None,
+            },
+        )
+        .await;
+
+        let provider =
+            EnvelopeProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(provider.get_order().await?, Order { id: 3 });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error_envelope_becomes_err() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount_response(
+            &mock_server,
+            Response {
+                status: Status::Error,
+                result: None,
+                error: Some("insufficient balance".to_string()),
+            },
+        )
+        .await;
+
+        let provider =
+            EnvelopeProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(
+            provider.get_order().await,
+            Err("API returned status Error: insufficient balance".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ok_envelope_without_data_is_err() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount_response(
+            &mock_server,
+            Response {
+                status: Status::Ok,
+                result: None,
+                error: None,
+            },
+        )
+        .await;
+
+        let provider =
+            EnvelopeProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(
+            provider.get_order().await,
+            Err("Response envelope has no `result` value".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_level_envelope() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount_response(
+            &mock_server,
+            Response {
+                status: Status::Error,
+                result: None,
+                error: Some("not found".to_string()),
+            },
+        )
+        .await;
+
+        let provider = EndpointEnvelopeProvider::new(
+            Url::from_str(&mock_server.uri())?,
+            Duration::from_secs(5),
+        );
+
+        assert_eq!(
+            provider.get_order().await,
+            Err("API returned status Error: not found".to_string())
+        );
+
+        // Endpoints without an envelope still return the raw body
+        let raw = provider.get_raw_order().await?;
+        assert_eq!(raw.status, Status::Error);
+        assert_eq!(raw.error, Some("not found".to_string()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+//! `environments` generates an enum of the declared base URLs and a `for_env` constructor,
+//! next to the plain URL constructors used against mock servers.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    http_provider!(
+        UserApi,
+        behavior_version: 2,
+        environments: {
+            dev: "https://dev.api.example.com/",
+            staging_eu: "https://staging.eu.example.com/api/v2",
+            prod: "https://api.example.com/",
+            local: "http://127.0.0.1:8080/",
+        },
+        {
+            { path: "/users", method: GET, fn_name: list, res: Vec<u32> },
+        }
+    );
+
+    #[test]
+    fn test_environment_urls() {
+        let expected = [
+            (UserApiEnv::Dev, "https://dev.api.example.com/"),
+            (
+                UserApiEnv::StagingEu,
+                "https://staging.eu.example.com/api/v2",
+            ),
+            (UserApiEnv::Prod, "https://api.example.com/"),
+            (UserApiEnv::Local, "http://127.0.0.1:8080/"),
+        ];
+        for (env, url) in expected {
+            assert_eq!(env.url().as_str(), url);
+        }
+
+        // Constructing a provider doesn't connect
+        let _provider = UserApi::for_env(UserApiEnv::Prod, Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_plain_url_constructor_remains() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .respond_with(ResponseTemplate::new(200).set_body_json([1, 2]))
+            .mount(&mock_server)
+            .await;
+
+        let provider = UserApi::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        assert_eq!(provider.list().await?, [1, 2]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+//! Endpoints declaring `err` deserialize non-2xx bodies into that type, and fall back to the
+//! provider's usual error when the body is something else.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct ApiError {
+        code: String,
+        message: String,
+        details: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct User {
+        name: String,
+    }
+
+    http_provider!(
+        ErrorBodyProvider,
+        behavior_version: 1,
+        {
+            { path: "/users", method: POST, fn_name: create, req: User, res: User, err: ApiError },
+        }
+    );
+
+    http_provider!(
+        ErrorBodyV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/users", method: POST, fn_name: create, req: User, res: User, err: ApiError },
+        }
+    );
+
+    const HTML: &str = "<html><body><h1>502 Bad Gateway</h1></body></html>";
+
+    async fn mock_server(status: u16, body: &str) -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .respond_with(ResponseTemplate::new(status).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    fn user() -> User {
+        User {
+            name: "ada".to_string(),
+        }
+    }
+
+    fn api_error() -> ApiError {
+        ApiError {
+            code: "invalid_name".to_string(),
+            message: "name is taken".to_string(),
+            details: vec!["name".to_string()],
+        }
+    }
+
+    const API_ERROR: &str =
+        r#"{"code": "invalid_name", "message": "name is taken", "details": ["name"]}"#;
+
+    #[tokio::test]
+    async fn test_error_body_is_deserialized() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server(400, API_ERROR).await;
+        let url = Url::from_str(&mock_server.uri())?;
+
+        let error = ErrorBodyProvider::new(url.clone(), Duration::from_secs(5))
+            .create(&user())
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("HTTP request failed with status 400: Bad Request: ApiError {"));
+        match error {
+            ErrorBodyProviderApiError::Api { code, error } => {
+                assert_eq!((code, error), (400, api_error()))
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        match ErrorBodyV2Provider::new(url, Duration::from_secs(5))
+            .create(&user())
+            .await
+            .unwrap_err()
+        {
+            ErrorBodyV2ProviderApiError::Api { code, error } => {
+                assert_eq!((code, error), (400, api_error()))
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_other_bodies_fall_back() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server(500, HTML).await;
+        let url = Url::from_str(&mock_server.uri())?;
+
+        let error = ErrorBodyProvider::new(url.clone(), Duration::from_secs(5))
+            .create(&user())
+            .await
+            .unwrap_err();
+        match &error {
+            ErrorBodyProviderApiError::Other(message) => assert_eq!(
+                message,
+                &format!(
+                    "HTTP request failed with status 500: Internal Server Error: {}",
+                    HTML
+                )
+            ),
+            error => panic!("unexpected error: {:?}", error),
+        }
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "HTTP request failed with status 500: Internal Server Error: {}",
+                HTML
+            )
+        );
+
+        let error = ErrorBodyV2Provider::new(url, Duration::from_secs(5))
+            .create(&user())
+            .await
+            .unwrap_err();
+        assert!(std::error::Error::source(&error).is_some());
+        match error {
+            ErrorBodyV2ProviderApiError::Other(ErrorBodyV2ProviderError::Status { code, body }) => {
+                assert_eq!((code, body.as_str()), (500, HTML))
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_success_and_transport_errors_are_unchanged(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server(200, r#"{"name": "ada"}"#).await;
+        let provider =
+            ErrorBodyV2Provider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        assert_eq!(provider.create(&user()).await?, user());
+
+        // Nothing listens on the port of a dropped listener
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let url = Url::from_str(&format!("http://{}", listener.local_addr()?))?;
+        drop(listener);
+        let error = ErrorBodyProvider::new(url, Duration::from_secs(5))
+            .create(&user())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&error, ErrorBodyProviderApiError::Other(message) if message.starts_with("Request failed: ")),
+            "{:?}",
+            error
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+//! `error_context` providers wrap their errors in a `Context` naming the failing call, so
+//! `anyhow` chains read like `.context()` was added by hand. Run with
+//! `cargo test --features anyhow-context`.
+#![cfg(feature = "anyhow-context")]
+
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct UserPath {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct Auth {
+        token: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ApiError {
+        message: String,
+    }
+
+    http_provider!(
+        UserProvider,
+        error_context: true,
+        behavior_version: 2,
+        {
+            { path: "/users/{id}", method: GET, fn_name: get_user, path_params: UserPath, query_params: Auth, res: User },
+            { path: "/users/{id}", method: GET, fn_name: get_user_or_api_error, path_params: UserPath, res: User, err: ApiError },
+        }
+    );
+
+    #[tokio::test]
+    async fn test_chain_names_the_call() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/7"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("maintenance"))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url.clone(), Duration::from_secs(5));
+
+        let auth = Auth {
+            token: "s3cret".to_string(),
+        };
+        let error = provider
+            .get_user(&UserPath { id: 7 }, &auth)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.without_context(),
+            UserProviderError::Status { code: 503, .. }
+        ));
+
+        let chain = format!("{:#}", anyhow::Error::from(error));
+        let host = url.host_str().unwrap();
+        assert!(chain.starts_with("`get_user` GET http://"), "{}", chain);
+        assert!(chain.contains(host), "{}", chain);
+        assert!(chain.contains("/users/7: HTTP request failed with status 503"));
+        assert!(!chain.contains("s3cret"), "{}", chain);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_api_errors_are_left_alone() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/7"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_json(serde_json::json!({ "message": "gone" })),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/8"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .mount(&mock_server)
+            .await;
+        let provider =
+            UserProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        match provider.get_user_or_api_error(&UserPath { id: 7 }).await {
+            Err(UserProviderApiError::Api { code, error }) => {
+                assert_eq!(code, 404);
+                assert_eq!(error.message, "gone");
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        // Failures other than the declared error body are wrapped
+        let error = provider
+            .get_user_or_api_error(&UserPath { id: 8 })
+            .await
+            .unwrap_err();
+        let chain = format!("{:#}", anyhow::Error::from(error));
+        assert!(
+            chain.starts_with("`get_user_or_api_error` GET http://"),
+            "{}",
+            chain
+        );
+        assert!(chain.contains("/users/8: Failed to deserialize response: "));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,301 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Item {
+        id: u32,
+    }
+
+    http_provider!(
+        AlertingProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/items",
+                method: GET,
+                fn_name: get_item,
+                res: Item,
+            },
+            {
+                path: "/missing",
+                method: DELETE,
+                fn_name: delete_missing,
+                res: Item,
+            },
+            {
+                path: "/garbage",
+                method: GET,
+                fn_name: get_garbage,
+                res: Item,
+            },
+        }
+    );
+
+    #[derive(Debug, Deserialize)]
+    struct ApiError {
+        message: String,
+    }
+
+    http_provider!(
+        TypedAlertingProvider,
+        behavior_version: 2,
+        {
+            { path: "/items", method: GET, fn_name: get_item, res: Item },
+            { path: "/missing", method: DELETE, fn_name: delete_missing, res: Item },
+            { path: "/garbage", method: GET, fn_name: get_garbage, res: Item },
+            { path: "/shapeless", method: GET, fn_name: get_shapeless, res: Item },
+            { path: "/missing", method: DELETE, fn_name: delete_or_api_error, res: Item, err: ApiError },
+        }
+    );
+
+    type Recorded = Arc<Mutex<Vec<(AlertingProviderEndpointInfo, String)>>>;
+
+    fn recording_provider(url: Url) -> (AlertingProvider, Recorded) {
+        let recorded: Recorded = Arc::default();
+        let sink = recorded.clone();
+        let provider = AlertingProvider::new(url, Duration::from_secs(5)).with_error_hook(
+            move |endpoint, error| {
+                sink.lock().unwrap().push((*endpoint, error.to_string()));
+            },
+        );
+        (provider, recorded)
+    }
+
+    async fn mount(mock_server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Item { id: 1 }))
+            .mount(mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/garbage"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>"))
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_error_hook_sees_every_failure() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+
+        let (provider, recorded) = recording_provider(Url::from_str(&mock_server.uri())?);
+
+        assert_eq!(provider.get_item().await?, Item { id: 1 });
+        assert!(recorded.lock().unwrap().is_empty());
+
+        let status_error = provider.delete_missing().await.unwrap_err();
+        let deserialize_error = provider.get_garbage().await.unwrap_err();
+
+        assert_eq!(
+            *recorded.lock().unwrap(),
+            vec![
+                (
+                    AlertingProviderEndpointInfo {
+                        name: "delete_missing",
+                        method: "DELETE",
+                        path: Some("/missing"),
+                    },
+                    status_error,
+                ),
+                (
+                    AlertingProviderEndpointInfo {
+                        name: "get_garbage",
+                        method: "GET",
+                        path: Some("/garbage"),
+                    },
+                    deserialize_error,
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error_hook_sees_transport_failures() -> Result<(), Box<dyn std::error::Error>> {
+        // Nothing listens on the port once the listener is dropped
+        let port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+        let url = Url::from_str(&format!("http://127.0.0.1:{}", port))?;
+
+        let (provider, recorded) = recording_provider(url);
+
+        let error = provider.get_item().await.unwrap_err();
+        assert!(error.starts_with("Request failed"));
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].0.name, "get_item");
+        assert_eq!(recorded[0].1, error);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_panicking_error_hook_keeps_error() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+
+        let provider =
+            AlertingProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_error_hook(|_, _| panic!("alerting backend down"));
+
+        assert_eq!(
+            provider.delete_missing().await,
+            Err("HTTP request failed with status 404: Not Found".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_version_2_hook_receives_typed_error() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+        Mock::given(method("GET"))
+            .and(path("/shapeless"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": "one" })),
+            )
+            .mount(&mock_server)
+            .await;
+        let recorded: Arc<Mutex<Vec<(&'static str, TypedAlertingProviderError)>>> = Arc::default();
+        let sink = recorded.clone();
+        let provider =
+            TypedAlertingProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_error_hook(move |endpoint, error| {
+                    sink.lock().unwrap().push((endpoint.name, error.clone()));
+                });
+
+        provider.delete_missing().await.unwrap_err();
+        provider.get_garbage().await.unwrap_err();
+        provider.get_shapeless().await.unwrap_err();
+        provider.delete_or_api_error().await.unwrap_err();
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+        let unreachable = Url::from_str(&format!("http://127.0.0.1:{}", port))?;
+        let sink = recorded.clone();
+        TypedAlertingProvider::new(unreachable, Duration::from_secs(5))
+            .with_error_hook(move |endpoint, error| {
+                sink.lock().unwrap().push((endpoint.name, error.clone()));
+            })
+            .get_item()
+            .await
+            .unwrap_err();
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded.len(), 5);
+        assert!(matches!(
+            &recorded[0],
+            (
+                "delete_missing",
+                TypedAlertingProviderError::Status { code: 404, .. }
+            )
+        ));
+        assert!(matches!(
+            &recorded[1],
+            ("get_garbage", TypedAlertingProviderError::NotJson { .. })
+        ));
+        assert!(matches!(
+            &recorded[2],
+            (
+                "get_shapeless",
+                TypedAlertingProviderError::SchemaMismatch { .. }
+            )
+        ));
+        // No body matched `ApiError`, so the status error itself is reported
+        assert!(matches!(
+            &recorded[3],
+            (
+                "delete_or_api_error",
+                TypedAlertingProviderError::Status { code: 404, .. }
+            )
+        ));
+        match &recorded[4] {
+            ("get_item", TypedAlertingProviderError::Request(source)) => {
+                assert!(source.is_connect(), "{}", source)
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_decoded_error_body_reaches_hook_as_status(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/missing"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_json(serde_json::json!({ "message": "gone" })),
+            )
+            .mount(&mock_server)
+            .await;
+        let recorded: Arc<Mutex<Vec<TypedAlertingProviderError>>> = Arc::default();
+        let sink = recorded.clone();
+        let provider =
+            TypedAlertingProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_error_hook(move |_, error| sink.lock().unwrap().push(error.clone()));
+
+        match provider.delete_or_api_error().await.unwrap_err() {
+            TypedAlertingProviderApiError::Api { code: 404, error } => {
+                assert_eq!(error.message, "gone")
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        match &recorded.lock().unwrap()[..] {
+            [TypedAlertingProviderError::Status { code: 404, body }] => {
+                assert!(body.contains("gone"), "{}", body)
+            }
+            recorded => panic!("unexpected errors: {:?}", recorded),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_hook_spawns_async_reporting() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let provider =
+            TypedAlertingProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_error_hook(move |endpoint, error| {
+                    let sender = sender.clone();
+                    let report = format!("{} failed: {}", endpoint.name, error);
+                    // The hook returns at once; the report is sent by the task
+                    tokio::spawn(async move {
+                        tokio::task::yield_now().await;
+                        let _ = sender.send(report);
+                    });
+                });
+
+        provider.delete_missing().await.unwrap_err();
+
+        let report = receiver.recv().await.expect("report must be sent");
+        assert!(report.starts_with("delete_missing failed: HTTP request failed with status 404"));
+
+        Ok(())
+    }
+}
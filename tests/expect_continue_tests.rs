@@ -0,0 +1,55 @@
+//! Pins down how reqwest treats `Expect: 100-continue`, which is why the macro rejects the
+//! `expect_continue` option. wiremock always reads the full request, so a raw server is used.
+mod support;
+
+#[cfg(test)]
+mod tests {
+    use crate::support::{ScriptedResponse, ScriptedServer};
+    use http_provider_macro::http_provider;
+    use reqwest::header::HeaderMap;
+    use std::time::Duration;
+
+    http_provider!(
+        UploadProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/uploads",
+                method: POST,
+                fn_name: upload,
+                req: String,
+                res: String,
+                headers: HeaderMap,
+            },
+        }
+    );
+
+    #[tokio::test]
+    async fn test_body_is_sent_without_interim_response() -> Result<(), Box<dyn std::error::Error>>
+    {
+        // The server never sends `100 Continue`; it reads the request and rejects it
+        let server = ScriptedServer::start(
+            ScriptedResponse::new(401)
+                .header("content-type", "text/plain")
+                .body("unauthorized")
+                .into_script(),
+        )
+        .await;
+
+        let provider = UploadProvider::new(server.url(), Duration::from_secs(2));
+        let body = "x".repeat(256 * 1024);
+        let mut headers = HeaderMap::new();
+        headers.insert("expect", "100-continue".parse()?);
+
+        let error = provider.upload(&body, headers).await.unwrap_err();
+        assert!(error.contains("401"), "{}", error);
+
+        // The whole body arrived although the server never allowed it to be sent
+        let received = server.received();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].header("expect"), Some("100-continue"));
+        assert_eq!(received[0].body, serde_json::to_vec(&body)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+//! `expect_status` endpoints require one specific status instead of accepting any 2xx.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct ApiError {
+        code: String,
+    }
+
+    http_provider!(
+        UserProvider,
+        behavior_version: 1,
+        {
+            { path: "/users", method: POST, fn_name: create_user, req: User, res: User, expect_status: 201 },
+            { path: "/users/gone", method: GET, fn_name: gone, res: (), expect_status: 410 },
+        }
+    );
+
+    http_provider!(
+        UserV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/users", method: POST, fn_name: create_user, req: User, res: User, err: ApiError, expect_status: 201 },
+        }
+    );
+
+    async fn server_answering(status: u16) -> MockServer {
+        let mock_server = MockServer::start().await;
+        let response = if status < 300 {
+            ResponseTemplate::new(status).set_body_json(User { id: 1 })
+        } else {
+            ResponseTemplate::new(status).set_body_json(serde_json::json!({ "code": "busy" }))
+        };
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/gone"))
+            .respond_with(ResponseTemplate::new(status))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_expected_status_succeeds() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = server_answering(201).await;
+        let url = Url::from_str(&mock_server.uri())?;
+
+        let provider = UserProvider::new(url.clone(), Duration::from_secs(5));
+        assert_eq!(provider.create_user(&User { id: 1 }).await?, User { id: 1 });
+        let provider = UserV2Provider::new(url, Duration::from_secs(5));
+        assert_eq!(provider.create_user(&User { id: 1 }).await?, User { id: 1 });
+
+        // The expected status passes even when it isn't a success
+        let mock_server = server_answering(410).await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url, Duration::from_secs(5));
+        provider.gone().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_other_statuses_fail() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = server_answering(202).await;
+        let url = Url::from_str(&mock_server.uri())?;
+
+        let provider = UserProvider::new(url.clone(), Duration::from_secs(5));
+        assert_eq!(
+            provider.create_user(&User { id: 1 }).await,
+            Err("Expected status 201, got 202".to_string())
+        );
+        assert_eq!(
+            provider.gone().await,
+            Err("Expected status 410, got 202".to_string())
+        );
+        let provider = UserV2Provider::new(url, Duration::from_secs(5));
+        match provider.create_user(&User { id: 1 }).await.unwrap_err() {
+            UserV2ProviderApiError::Other(UserV2ProviderError::Other(message)) => {
+                assert_eq!(message, "Expected status 201, got 202")
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        // Error statuses are still reported with their body
+        let mock_server = server_answering(503).await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserV2Provider::new(url, Duration::from_secs(5));
+        match provider.create_user(&User { id: 1 }).await.unwrap_err() {
+            UserV2ProviderApiError::Api { code, error } => {
+                assert_eq!((code, error.code.as_str()), (503, "busy"))
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum PriorityClass {
+        Interactive,
+        Batch,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Job {
+        id: u32,
+    }
+
+    http_provider!(
+        JobsProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/jobs",
+                method: POST,
+                fn_name: submit_job,
+                req: Job,
+                res: Job,
+                extension: PriorityClass,
+            },
+        }
+    );
+
+    #[tokio::test]
+    async fn test_extension_reaches_hook() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/jobs"))
+            .and(header("x-priority", "batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Job { id: 9 }))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            JobsProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_extension_hook(|request, extension| {
+                    let priority = match extension.downcast_ref::<PriorityClass>() {
+                        Some(PriorityClass::Interactive) => "interactive",
+                        Some(PriorityClass::Batch) => "batch",
+                        None => return,
+                    };
+                    request
+                        .headers_mut()
+                        .insert("x-priority", priority.parse().unwrap());
+                });
+
+        assert_eq!(
+            provider
+                .submit_job(&Job { id: 9 }, PriorityClass::Batch)
+                .await?,
+            Job { id: 9 }
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_extension_without_hook_is_ignored() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/jobs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Job { id: 9 }))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            JobsProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(
+            provider
+                .submit_job(&Job { id: 9 }, PriorityClass::Interactive)
+                .await?,
+            Job { id: 9 }
+        );
+
+        Ok(())
+    }
+}
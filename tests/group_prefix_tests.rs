@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    http_provider!(
+        ForgeProvider,
+        behavior_version: 1,
+        {
+            orgs: {
+                prefix: "/orgs/{org_id}",
+                group_params: OrgPath,
+                {
+                    path: "/members",
+                    method: GET,
+                    fn_name: members,
+                    res: Vec<String>,
+                },
+                {
+                    method: GET,
+                    fn_name: get,
+                    res: String,
+                },
+                repos: {
+                    prefix: "/repos/{repo}",
+                    group_params: RepoPath,
+                    {
+                        path: "/issues/{number}",
+                        method: GET,
+                        fn_name: issue,
+                        path_params: IssuePath,
+                        res: Issue,
+                    },
+                },
+                teams: {
+                    prefix: "/teams",
+                    {
+                        path: "/{slug}",
+                        method: GET,
+                        fn_name: get,
+                        path_params: TeamPath,
+                        res: String,
+                    },
+                },
+            },
+        }
+    );
+
+    #[derive(Serialize)]
+    struct OrgPath {
+        org_id: String,
+    }
+
+    #[derive(Serialize)]
+    struct RepoPath {
+        repo: String,
+    }
+
+    #[derive(Serialize)]
+    struct IssuePath {
+        number: u32,
+    }
+
+    #[derive(Serialize)]
+    struct TeamPath {
+        slug: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Issue {
+        title: String,
+    }
+
+    fn acme() -> OrgPath {
+        OrgPath {
+            org_id: "acme".to_string(),
+        }
+    }
+
+    async fn mock(server: &MockServer, request_path: &str, body: impl Serialize) {
+        Mock::given(method("GET"))
+            .and(path(request_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_nested_group_params() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let issue = Issue {
+            title: "crash on start".to_string(),
+        };
+        mock(&mock_server, "/orgs/acme/repos/widgets/issues/7", &issue).await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ForgeProvider::new(url, Duration::from_secs(5));
+
+        let repo = RepoPath {
+            repo: "widgets".to_string(),
+        };
+        let received = provider
+            .orgs()
+            .repos()
+            .issue(&acme(), &repo, &IssuePath { number: 7 })
+            .await?;
+        assert_eq!(received, issue);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prefix_applies_to_every_member() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mock(&mock_server, "/orgs/acme/members", ["alice"]).await;
+        mock(&mock_server, "/orgs/acme", "Acme").await;
+        mock(&mock_server, "/orgs/acme/teams/core", "Core").await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ForgeProvider::new(url, Duration::from_secs(5));
+        let orgs = provider.orgs();
+
+        assert_eq!(orgs.members(&acme()).await?, ["alice"]);
+        assert_eq!(orgs.get(&acme()).await?, "Acme");
+        let team = TeamPath {
+            slug: "core".to_string(),
+        };
+        assert_eq!(orgs.teams().get(&acme(), &team).await?, "Core");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_template_includes_prefixes() {
+        assert_eq!(
+            ForgeProviderOrgsRepos::ISSUE_PATH_TEMPLATE,
+            "/orgs/{org_id}/repos/{repo}/issues/{number}"
+        );
+        assert_eq!(
+            ForgeProviderOrgsRepos::issue_path_regex(),
+            "^/orgs/[^/]+/repos/[^/]+/issues/[^/]+$"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    http_provider!(
+        ShopProvider,
+        behavior_version: 1,
+        {
+            users: {
+                {
+                    path: "/users/{id}",
+                    method: GET,
+                    fn_name: get_by_id,
+                    path_params: IdPath,
+                    res: User,
+                },
+                {
+                    path: "/users",
+                    method: GET,
+                    fn_name: list,
+                    res: Vec<User>,
+                },
+            },
+            orders: {
+                {
+                    path: "/orders/{id}",
+                    method: GET,
+                    fn_name: get_by_id,
+                    path_params: IdPath,
+                    res: Order,
+                },
+                {
+                    path: "/orders",
+                    method: POST,
+                    fn_name: create,
+                    req: Order,
+                    res: Order,
+                },
+            },
+            {
+                path: "/health",
+                method: GET,
+                fn_name: health,
+                res: Health,
+            },
+        }
+    );
+
+    #[derive(Serialize, Deserialize)]
+    struct IdPath {
+        id: u32,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct User {
+        name: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Order {
+        item: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Health {
+        ok: bool,
+    }
+
+    #[tokio::test]
+    async fn test_grouped_methods_with_same_name() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(User {
+                name: "alice".to_string(),
+            }))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orders/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Order {
+                item: "book".to_string(),
+            }))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ShopProvider::new(url, Duration::from_secs(5));
+
+        let user = provider.users().get_by_id(&IdPath { id: 7 }).await?;
+        let order = provider.orders().get_by_id(&IdPath { id: 7 }).await?;
+
+        assert_eq!(user.name, "alice");
+        assert_eq!(order.item, "book");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_group_shares_provider_configuration() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        let order = Order {
+            item: "book".to_string(),
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/orders"))
+            .and(body_json(&order))
+            .and(wiremock::matchers::header("x-org-id", "acme"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&order))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Health { ok: true }))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ShopProvider::new(url, Duration::from_secs(5)).with_header_context(|| {
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert("x-org-id", "acme".parse().ok()?);
+            Some(headers)
+        });
+
+        let orders = provider.orders();
+        assert_eq!(orders.create(&order).await?, order);
+        assert_eq!(provider.health().await?, Health { ok: true });
+
+        Ok(())
+    }
+}
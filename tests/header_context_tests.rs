@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, Url};
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    tokio::task_local! {
+        static ORG_ID: String;
+    }
+
+    http_provider!(
+        TenantProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/items",
+                method: GET,
+                fn_name: list_items,
+                res: Vec<Item>,
+            },
+            {
+                path: "/items",
+                method: POST,
+                fn_name: create_item,
+                req: Item,
+                res: Item,
+                headers: reqwest::header::HeaderMap,
+            },
+        }
+    );
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Item {
+        name: String,
+    }
+
+    fn org_headers() -> Option<HeaderMap> {
+        ORG_ID
+            .try_with(|org_id| {
+                let mut headers = HeaderMap::new();
+                headers.insert("x-org-id", org_id.parse().ok()?);
+                Some(headers)
+            })
+            .ok()
+            .flatten()
+    }
+
+    #[tokio::test]
+    async fn test_context_header_applied_without_headers_param(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .and(header("x-org-id", "acme"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![Item {
+                name: "widget".to_string(),
+            }]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider =
+            TenantProvider::new(url, Duration::from_secs(5)).with_header_context(org_headers);
+
+        let result = ORG_ID
+            .scope("acme".to_string(), provider.list_items())
+            .await?;
+
+        assert_eq!(
+            result,
+            vec![Item {
+                name: "widget".to_string()
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_context_header_absent_outside_scope() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .and(header("x-org-id", "acme"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Vec::<Item>::new()))
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider =
+            TenantProvider::new(url, Duration::from_secs(5)).with_header_context(org_headers);
+
+        let result = provider.list_items().await?;
+        assert!(result.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_explicit_headers_override_context() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/items"))
+            .and(header("x-org-id", "override"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Item {
+                name: "created".to_string(),
+            }))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider =
+            TenantProvider::new(url, Duration::from_secs(5)).with_header_context(org_headers);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-org-id", "override".parse()?);
+
+        let result = ORG_ID
+            .scope(
+                "acme".to_string(),
+                provider.create_item(
+                    &Item {
+                        name: "created".to_string(),
+                    },
+                    headers,
+                ),
+            )
+            .await?;
+
+        assert_eq!(result.name, "created");
+
+        Ok(())
+    }
+}
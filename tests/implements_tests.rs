@@ -0,0 +1,103 @@
+//! The `implements` provider option implements a contract trait with one method per top-level
+//! endpoint.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::future::Future;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct UserPath {
+        id: u32,
+    }
+
+    /// The contract every `UserProvider` must fulfill.
+    trait ApiContract {
+        fn list_users(&self) -> impl Future<Output = Result<Vec<User>, String>> + Send;
+        async fn get_user(&self, path_params: &UserPath) -> Result<User, String>;
+        async fn create_user(&self, body: &User) -> Result<User, String>;
+    }
+
+    /// A trait one endpoint also implements on its own.
+    trait Health {
+        async fn ping(&self) -> Result<Vec<User>, String>;
+    }
+
+    http_provider!(
+        UserProvider,
+        behavior_version: 1,
+        implements: ApiContract,
+        {
+            { path: "/users", method: GET, fn_name: list_users, res: Vec<User>, trait_impl: Health, trait_fn: ping },
+            { path: "/users/{id}", method: GET, fn_name: get_user, path_params: UserPath, res: User },
+            { path: "/users", method: POST, fn_name: create_user, req: User, res: User },
+            admin: {
+                { path: "/admin/users", method: GET, fn_name: list_users, res: Vec<User> },
+            },
+        }
+    );
+
+    /// Calls the provider through the contract only.
+    async fn through_contract(api: &impl ApiContract) -> Result<(Vec<User>, User, User), String> {
+        let users = api.list_users().await?;
+        let user = api.get_user(&UserPath { id: 1 }).await?;
+        let created = api.create_user(&User { id: 2 }).await?;
+        Ok((users, user, created))
+    }
+
+    #[tokio::test]
+    async fn test_contract_methods_delegate_to_endpoints() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![User { id: 1 }]))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(User { id: 1 }))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .and(body_json(User { id: 2 }))
+            .respond_with(ResponseTemplate::new(201).set_body_json(User { id: 2 }))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/admin/users"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Vec::<User>::new()))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url, Duration::from_secs(5));
+
+        let (users, user, created) = through_contract(&provider).await?;
+        assert_eq!(users, vec![User { id: 1 }]);
+        assert_eq!(user, User { id: 1 });
+        assert_eq!(created, User { id: 2 });
+
+        // The inherent methods, other traits and groups are unaffected
+        assert_eq!(
+            UserProvider::get_user(&provider, &UserPath { id: 1 }).await?,
+            user
+        );
+        assert_eq!(Health::ping(&provider).await?, users);
+        assert!(provider.admin().list_users().await?.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+//! `json_options` serializes request bodies with the configured formatter, and the request
+//! signer sees exactly the bytes that are sent.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Serialize;
+    use serde_json::ser::Formatter;
+    use std::time::Duration;
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+        str::FromStr,
+    };
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct Quote {
+        symbol: String,
+        price: f64,
+        tags: Vec<String>,
+    }
+
+    /// Writes floats in positional notation instead of `serde_json`'s `1e20`.
+    struct PositionalFloats;
+
+    impl Formatter for PositionalFloats {
+        fn write_f64<W: ?Sized + std::io::Write>(
+            &mut self,
+            writer: &mut W,
+            value: f64,
+        ) -> std::io::Result<()> {
+            write!(writer, "{}", value)
+        }
+    }
+
+    fn positional_floats() -> PositionalFloats {
+        PositionalFloats
+    }
+
+    http_provider!(
+        QuoteProvider,
+        behavior_version: 2,
+        {
+            { path: "/compact", method: POST, fn_name: compact, req: Quote, res: u32 },
+            { path: "/pretty", method: POST, fn_name: pretty, req: Quote, res: u32, json_options: { pretty: true } },
+            { path: "/positional", method: POST, fn_name: positional, req: Quote, res: u32, json_options: { formatter: positional_floats } },
+            { path: "/typed", method: POST, fn_name: typed, req: Quote, res: u32, content_type: "application/vnd.quote+json", json_options: { pretty: true } },
+        }
+    );
+
+    fn quote() -> Quote {
+        Quote {
+            symbol: "ACME".to_string(),
+            price: 1e20,
+            tags: vec!["a".to_string()],
+        }
+    }
+
+    fn body_hash(body: &[u8]) -> String {
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Returns the body of every received request, with the `x-body-hash` header set on it.
+    async fn received(mock_server: &MockServer) -> Vec<(String, String)> {
+        let header = |request: &Request| {
+            request
+                .headers
+                .iter()
+                .find(|(name, _)| name.as_str() == "x-body-hash")
+                .map(|(_, values)| values.last().as_str().to_string())
+                .unwrap_or_default()
+        };
+        mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| {
+                (
+                    String::from_utf8(request.body.clone()).unwrap(),
+                    header(request),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_bodies_use_configured_serializer() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(1))
+            .mount(&mock_server)
+            .await;
+        // The signer hashes the body it saw, which must be the one sent
+        let provider =
+            QuoteProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_request_signer(|request| {
+                    let hash = request
+                        .body()
+                        .and_then(|body| body.as_bytes())
+                        .map(body_hash)
+                        .ok_or_else(|| "body is not buffered".to_string())?;
+                    request
+                        .headers_mut()
+                        .insert("x-body-hash", hash.parse().unwrap());
+                    Ok(())
+                });
+
+        provider.compact(&quote()).await?;
+        provider.pretty(&quote()).await?;
+        provider.positional(&quote()).await?;
+        provider.typed(&quote()).await?;
+
+        let pretty =
+            "{\n  \"symbol\": \"ACME\",\n  \"price\": 1e20,\n  \"tags\": [\n    \"a\"\n  ]\n}";
+        let expected = [
+            r#"{"symbol":"ACME","price":1e20,"tags":["a"]}"#,
+            pretty,
+            r#"{"symbol":"ACME","price":100000000000000000000,"tags":["a"]}"#,
+            pretty,
+        ];
+        let received = received(&mock_server).await;
+        assert_eq!(received.len(), expected.len());
+        for ((body, signed), expected) in received.iter().zip(expected) {
+            assert_eq!(body, expected);
+            assert_eq!(signed, &body_hash(expected.as_bytes()));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_content_type_is_unchanged() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pretty"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(1))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/typed"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(2))
+            .mount(&mock_server)
+            .await;
+        let provider =
+            QuoteProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(provider.pretty(&quote()).await?, 1);
+        assert_eq!(provider.typed(&quote()).await?, 2);
+
+        let content_types: Vec<_> = mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| {
+                request
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name.as_str() == "content-type")
+                    .map(|(_, values)| values.last().as_str().to_string())
+            })
+            .collect();
+        assert_eq!(
+            content_types,
+            [
+                Some("application/json".to_string()),
+                Some("application/vnd.quote+json".to_string())
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+//! Providers declaring `legacy_ctor: true` keep the deprecated millisecond constructors.
+#![allow(deprecated)]
+
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use std::str::FromStr;
+    use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+    http_provider!(
+        LegacyProvider,
+        behavior_version: 1,
+        legacy_ctor: true,
+        {
+            {
+                path: "/health",
+                method: GET,
+                fn_name: health,
+                res: String,
+            },
+        }
+    );
+
+    #[tokio::test]
+    async fn test_legacy_constructors() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json("ok"))
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = LegacyProvider::new(url.clone(), Some(5000));
+        assert_eq!(provider.health().await?, "ok");
+
+        let provider = LegacyProvider::try_new(url, None)?;
+        assert_eq!(provider.health().await?, "ok");
+
+        Ok(())
+    }
+}
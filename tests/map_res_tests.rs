@@ -0,0 +1,146 @@
+//! `map_res` endpoints decode the body into the declared `res` and return what the mapper
+//! function makes of it, such as the payload of an API-specific envelope.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u32,
+        name: String,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct ApiEnvelope<T> {
+        ok: bool,
+        payload: Option<T>,
+    }
+
+    mod mappers {
+        use super::{ApiEnvelope, User};
+
+        pub fn unwrap_user(envelope: ApiEnvelope<User>) -> Result<User, String> {
+            super::unwrap_payload(envelope)
+        }
+    }
+
+    fn unwrap_payload<T>(envelope: ApiEnvelope<T>) -> Result<T, String> {
+        match envelope {
+            ApiEnvelope {
+                ok: true,
+                payload: Some(payload),
+            } => Ok(payload),
+            _ => Err("API call was not ok".to_string()),
+        }
+    }
+
+    fn user_name(user: User) -> Result<String, String> {
+        Ok(user.name)
+    }
+
+    http_provider!(
+        UserProvider,
+        behavior_version: 1,
+        {
+            { path: "/users/7", method: GET, fn_name: get_user, res: ApiEnvelope<User>, map_res: mappers::unwrap_user -> User },
+            { path: "/users", method: GET, fn_name: list_users, res: ApiEnvelope<Vec<User>>, map_res: unwrap_payload -> Vec<User> },
+            { path: "/users/7", method: GET, fn_name: get_user_raw, res: ApiEnvelope<User>, map_res: unwrap_payload -> User, keep_raw_body: true },
+            { path: "/wrapped/7", method: GET, fn_name: get_user_name, res: User, map_res: user_name -> String, envelope: { status_field: status, ok_value: "Ok", error_field: error, data_field: result } },
+        }
+    );
+
+    fn strict_user(envelope: ApiEnvelope<User>) -> Result<User, UserV2ProviderError> {
+        unwrap_payload(envelope).map_err(UserV2ProviderError::Other)
+    }
+
+    http_provider!(
+        UserV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/users/7", method: GET, fn_name: get_user, res: ApiEnvelope<User>, map_res: strict_user -> User, not_found_as_none: true },
+        }
+    );
+
+    fn user() -> User {
+        User {
+            id: 7,
+            name: "Ada".to_string(),
+        }
+    }
+
+    async fn mount(mock_server: &MockServer, route: &str, body: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_mapper_output_is_returned() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let envelope = serde_json::json!({ "ok": true, "payload": user() });
+        mount(&mock_server, "/users/7", envelope.clone()).await;
+        mount(
+            &mock_server,
+            "/users",
+            serde_json::json!({ "ok": true, "payload": [user()] }),
+        )
+        .await;
+        mount(
+            &mock_server,
+            "/wrapped/7",
+            serde_json::json!({ "status": "Ok", "result": user() }),
+        )
+        .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url.clone(), Duration::from_secs(5));
+
+        assert_eq!(provider.get_user().await?, user());
+        assert_eq!(provider.list_users().await?, vec![user()]);
+        let (mapped, raw_body) = provider.get_user_raw().await?;
+        assert_eq!(mapped, user());
+        assert_eq!(raw_body.as_ref(), serde_json::to_vec(&envelope)?.as_slice());
+        // The endpoint's envelope is unwrapped before the mapper runs
+        assert_eq!(provider.get_user_name().await?, "Ada");
+
+        let provider = UserV2Provider::new(url, Duration::from_secs(5));
+        assert_eq!(provider.get_user().await?, Some(user()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mapper_errors_are_returned() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount(
+            &mock_server,
+            "/users/7",
+            serde_json::json!({ "ok": false, "payload": null }),
+        )
+        .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url.clone(), Duration::from_secs(5));
+
+        assert_eq!(
+            provider.get_user().await.unwrap_err(),
+            "API call was not ok"
+        );
+
+        let provider = UserV2Provider::new(url, Duration::from_secs(5));
+        match provider.get_user().await.unwrap_err() {
+            UserV2ProviderError::Other(message) => assert_eq!(message, "API call was not ok"),
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        Ok(())
+    }
+}
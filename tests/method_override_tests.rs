@@ -0,0 +1,159 @@
+//! `method_override` endpoints are sent as POST with an `X-HTTP-Method-Override` header only
+//! when the provider is built with `method_override(true)`, so one binary serves both networks.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Serialize;
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+    use wiremock::{
+        matchers::{any, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct ItemPath {
+        id: u32,
+    }
+
+    http_provider!(
+        OverrideProvider,
+        behavior_version: 2,
+        method_override: true,
+        {
+            { path: "/items/{id}", method: PUT, fn_name: replace, path_params: ItemPath, req: serde_json::Value, res: u32 },
+            { path: "/items/{id}", method: DELETE, fn_name: delete, path_params: ItemPath, res: u32 },
+            { path: "/items/{id}", method: PATCH, fn_name: patch, path_params: ItemPath, req: serde_json::Value, res: u32, method_override: false },
+            { path: "/items", method: GET, fn_name: list, res: u32 },
+            { path: "/search", method: GET, fn_name: search, res: u32, method_override: true },
+            { path: "/items", method: POST, fn_name: create, req: serde_json::Value, res: u32 },
+        }
+    );
+
+    /// Returns the method, path and override header of every received request.
+    async fn requested(mock_server: &MockServer) -> Vec<(String, String, Option<String>)> {
+        mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|request| {
+                let override_header = request
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name.as_str().eq_ignore_ascii_case("x-http-method-override"))
+                    .map(|(_, values)| values.last().as_str().to_string());
+                (
+                    request.method.to_string(),
+                    request.url.path().to_string(),
+                    override_header,
+                )
+            })
+            .collect()
+    }
+
+    async fn call_all(provider: &OverrideProvider) -> Result<(), OverrideProviderError> {
+        let item = ItemPath { id: 7 };
+        let body = serde_json::json!({ "name": "ada" });
+        provider.replace(&item, &body).await?;
+        provider.delete(&item).await?;
+        provider.patch(&item, &body).await?;
+        provider.list().await?;
+        provider.search().await?;
+        provider.create(&body).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_methods_are_sent_as_written_by_default() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(1))
+            .mount(&mock_server)
+            .await;
+        let provider = OverrideProvider::builder(Url::from_str(&mock_server.uri())?).build()?;
+
+        call_all(&provider).await?;
+
+        let expected = [
+            ("PUT", "/items/7"),
+            ("DELETE", "/items/7"),
+            ("PATCH", "/items/7"),
+            ("GET", "/items"),
+            ("GET", "/search"),
+            ("POST", "/items"),
+        ];
+        let requested = requested(&mock_server).await;
+        assert_eq!(requested.len(), expected.len());
+        for ((method, path, override_header), expected) in requested.iter().zip(expected) {
+            assert_eq!((method.as_str(), path.as_str()), expected);
+            assert_eq!(override_header, &None);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_enabled_override_tunnels_through_post() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(1))
+            .mount(&mock_server)
+            .await;
+        let provider = OverrideProvider::builder(Url::from_str(&mock_server.uri())?)
+            .method_override(true)
+            .build()?;
+
+        call_all(&provider).await?;
+
+        // GET keeps its method unless opted in, and PATCH opted out
+        let expected = [
+            ("POST", "/items/7", Some("PUT")),
+            ("POST", "/items/7", Some("DELETE")),
+            ("PATCH", "/items/7", None),
+            ("GET", "/items", None),
+            ("POST", "/search", Some("GET")),
+            ("POST", "/items", None),
+        ];
+        let requested = requested(&mock_server).await;
+        assert_eq!(requested.len(), expected.len());
+        for ((method, path, override_header), expected) in requested.iter().zip(expected) {
+            assert_eq!(
+                (method.as_str(), path.as_str(), override_header.as_deref()),
+                expected
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_metadata_reports_logical_method() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/items/7"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let hook_reported = reported.clone();
+        let provider = OverrideProvider::builder(Url::from_str(&mock_server.uri())?)
+            .method_override(true)
+            .build()?
+            .with_error_hook(move |endpoint, _| hook_reported.lock().unwrap().push(*endpoint));
+
+        provider.delete(&ItemPath { id: 7 }).await.unwrap_err();
+
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(
+            (reported[0].name, reported[0].method, reported[0].path),
+            ("delete", "DELETE", Some("/items/{id}"))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+//! `codec: msgpack` endpoints send and decode MessagePack bodies. Run with
+//! `cargo test --features msgpack`.
+#![cfg(feature = "msgpack")]
+
+mod support;
+
+#[cfg(test)]
+mod tests {
+    crate::codec_tests!(
+        msgpack,
+        "application/msgpack",
+        rmp_serde::to_vec_named(&sample())?
+    );
+}
@@ -0,0 +1,99 @@
+//! Providers using every option, built with the minimum supported Rust version so generated
+//! code can't start requiring a newer compiler unnoticed. Only `trait_style: afit`, which
+//! emits native `async fn` in trait impls, needs Rust 1.75 and is left out.
+#![deny(warnings)]
+#![allow(dead_code)]
+
+use async_trait::async_trait;
+use http_provider_macro::http_provider;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct UserPath {
+    pub id: u32,
+}
+
+#[derive(Serialize)]
+pub struct OrgPath {
+    pub org: String,
+}
+
+#[derive(Serialize)]
+pub struct UserQuery {
+    pub page: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiFailure {
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct DocumentMeta {
+    pub title: String,
+}
+
+#[derive(Clone)]
+pub struct PageInfo {
+    pub total: u64,
+}
+
+pub struct Outbox;
+
+impl MsrvProviderResponseSink<User> for Outbox {
+    fn accept(&self, _: &User) {}
+}
+
+#[async_trait]
+pub trait UserStore {
+    async fn fetch_user(&self, path: &UserPath) -> Result<User, MsrvProviderError>;
+}
+
+http_provider!(
+    MsrvProvider,
+    behavior_version: 2,
+    api_version: "2024-01-01",
+    deadline_header: "x-deadline-ms",
+    routes_manifest: true,
+    method_override: true,
+    retry_transport_errors_once: true,
+    {
+        { path: "/users/{id}", method: GET, path_params: UserPath, query_params: UserQuery, headers: HeaderMap, res: User, retries: 2, not_found_as_none: true, with_meta: true },
+        { path: "/users/{id}", method: GET, fn_name: fetch_user, path_params: UserPath, res: User, trait_impl: UserStore, trait_style: async_trait },
+        { path: "/users", method: POST, req: User, res: User, err: ApiFailure, keep_raw_body: true, expect_status: 201, emit: Outbox },
+        { path: "/users/{id}", method: PUT, path_params: UserPath, req: User, res: User, optimistic_lock: true, args_struct: true },
+        { path: "/users", method: GET, fn_name: list, res: Vec<User>, single_flight: true, deserialize_off_thread: true, ttfb_timeout_ms: 500, res_headers: PageInfo { total: "x-total-count" } },
+        { path: "/login", method: POST, form: User, res: User, ok_statuses: [200, 202] },
+        { path: "/jobs", method: POST, req: User, res_map: { 200 => User, 202 => () } },
+        { path: "/documents", method: POST, res: User, multipart: { meta: DocumentMeta (json), files: many } },
+        { path: "/export", method: GET, range: true, res_format: bytes },
+        { path: "/download", method: GET, follow_presigned: true },
+        { path: "/latest", method: GET, capture_redirect: true },
+        { path: "/raw", method: GET, res_format: raw },
+        { path: "/events", method: GET, res: User, stream: ndjson },
+        { path: "/artifact", method: GET, download: true },
+        { path: "/blobs", method: PUT, upload_stream: true, res: User },
+        { path: "/blobs", method: PUT, fn_name: put_blob_with_progress, upload_stream: true, res: User, progress: true },
+        orgs: {
+            prefix: "/orgs/{org}",
+            group_params: OrgPath,
+            { path: "/members", method: GET, res: Vec<User>, envelope: { status_field: status, ok_value: "ok", error_field: error, data_field: data } },
+        },
+    }
+);
+
+http_provider!(
+    ScopedProvider,
+    behavior_version: 1,
+    call_scope: true,
+    {
+        { path: "/users/{id}", method: GET, path_params: UserPath, res: User },
+    }
+);
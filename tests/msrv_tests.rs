@@ -0,0 +1,35 @@
+//! Builds the consumer crate in `tests/msrv` with the minimum supported Rust version, which its
+//! `rust-toolchain.toml` selects. The toolchain and the crate's dependencies must be available,
+//! so the check is manual and opt-in: `cargo test --test msrv_tests -- --ignored`.
+//!
+//! The crate's `Cargo.lock` is committed: a fresh resolution picks the newest dependencies, some
+//! of which need a newer compiler than the generated code does. After changing the macro's
+//! dependencies, refresh it with `cargo update` in `tests/msrv`, downgrading with `--precise`
+//! whatever Rust 1.70 rejects.
+use std::path::Path;
+use std::process::Command;
+
+/// A `cargo` command run in the MSRV crate through rustup, without the toolchain selection
+/// cargo passes down to the tests it runs, so the crate's own `rust-toolchain.toml` applies.
+fn cargo(args: &[&str]) -> Command {
+    let mut command = Command::new("cargo");
+    command
+        .args(args)
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/msrv"))
+        .env_remove("RUSTUP_TOOLCHAIN")
+        .env_remove("RUSTC")
+        .env_remove("RUSTC_WRAPPER")
+        .env_remove("CARGO_TARGET_DIR");
+    command
+}
+
+#[test]
+#[ignore = "needs the Rust 1.70 toolchain, installed with `rustup toolchain install 1.70`"]
+fn test_generated_code_builds_with_the_minimum_supported_rust_version() {
+    let output = cargo(&["check", "--locked"]).output().expect("cargo runs");
+    assert!(
+        output.status.success(),
+        "the MSRV crate doesn't build:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
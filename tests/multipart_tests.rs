@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    http_provider!(
+        DocumentProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/documents",
+                method: POST,
+                fn_name: upload,
+                res: Receipt,
+                multipart: { meta: DocumentMeta (json), files: many },
+            },
+        }
+    );
+
+    #[derive(Serialize)]
+    struct DocumentMeta {
+        title: String,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Receipt {
+        id: u32,
+    }
+
+    fn meta() -> DocumentMeta {
+        DocumentMeta {
+            title: "Q3 report".to_string(),
+        }
+    }
+
+    async fn provider() -> Result<(MockServer, DocumentProvider), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/documents"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 1 })))
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = DocumentProvider::new(url, Duration::from_secs(5));
+        Ok((mock_server, provider))
+    }
+
+    /// Returns the boundary from the request's `Content-Type` and the body as text.
+    async fn received_form(mock_server: &MockServer) -> (String, String) {
+        let received = mock_server.received_requests().await.unwrap();
+        let request = &received[0];
+        let content_type = request
+            .headers
+            .get(&"content-type".into())
+            .unwrap()
+            .as_str();
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .expect(content_type)
+            .to_string();
+        (
+            boundary,
+            String::from_utf8_lossy(&request.body).into_owned(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_meta_and_files() -> Result<(), Box<dyn std::error::Error>> {
+        let (mock_server, provider) = provider().await?;
+
+        let files = vec![
+            (
+                "report.pdf".to_string(),
+                "application/pdf".to_string(),
+                Bytes::from_static(b"%PDF-1.7"),
+            ),
+            (
+                "data.csv".to_string(),
+                "text/csv".to_string(),
+                Bytes::from_static(b"a,b\n1,2\n"),
+            ),
+        ];
+        assert_eq!(provider.upload(&meta(), files).await?, Receipt { id: 1 });
+
+        let (boundary, body) = received_form(&mock_server).await;
+        let expected = [
+            format!("--{}\r\n", boundary),
+            "Content-Disposition: form-data; name=\"meta\"\r\nContent-Type: application/json\r\n\r\n{\"title\":\"Q3 report\"}\r\n".to_string(),
+            format!("--{}\r\n", boundary),
+            "Content-Disposition: form-data; name=\"files\"; filename=\"report.pdf\"\r\nContent-Type: application/pdf\r\n\r\n%PDF-1.7\r\n".to_string(),
+            format!("--{}\r\n", boundary),
+            "Content-Disposition: form-data; name=\"files\"; filename=\"data.csv\"\r\nContent-Type: text/csv\r\n\r\na,b\n1,2\n\r\n".to_string(),
+            format!("--{}--\r\n", boundary),
+        ];
+        assert_eq!(body, expected.concat());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_zero_files_still_sends_meta() -> Result<(), Box<dyn std::error::Error>> {
+        let (mock_server, provider) = provider().await?;
+
+        provider.upload(&meta(), Vec::new()).await?;
+
+        let (boundary, body) = received_form(&mock_server).await;
+        assert!(body.contains("name=\"meta\""), "{}", body);
+        assert!(!body.contains("filename="), "{}", body);
+        assert!(body.ends_with(&format!("--{}--\r\n", boundary)), "{}", body);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_file_names_are_sanitized() -> Result<(), Box<dyn std::error::Error>> {
+        let (mock_server, provider) = provider().await?;
+
+        let file = |name: &str| (name.to_string(), "text/plain".to_string(), Bytes::new());
+        let files = vec![
+            file("../../etc/passwd"),
+            file("C:\\Users\\me\\notes.txt"),
+            file("evil\"\r\nX-Injected: 1.txt"),
+            file("dir/"),
+        ];
+        provider.upload(&meta(), files).await?;
+
+        let (_, body) = received_form(&mock_server).await;
+        for name in ["passwd", "notes.txt", "evil___X-Injected: 1.txt", "file"] {
+            assert!(
+                body.contains(&format!("filename=\"{}\"\r\n", name)),
+                "{}",
+                body
+            );
+        }
+        assert!(!body.contains("\r\nX-Injected"), "{}", body);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_content_type() -> Result<(), Box<dyn std::error::Error>> {
+        let (mock_server, provider) = provider().await?;
+
+        let files = vec![(
+            "a.txt".to_string(),
+            "text/plain\r\nX-Injected: 1".to_string(),
+            Bytes::new(),
+        )];
+        let error = provider.upload(&meta(), files).await.unwrap_err();
+        assert!(error.starts_with("Invalid content type"), "{}", error);
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+
+        Ok(())
+    }
+}
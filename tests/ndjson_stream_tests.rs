@@ -0,0 +1,164 @@
+//! `stream: ndjson` endpoints return the items of a newline-delimited JSON body as a stream,
+//! decoding each line as it arrives. The scripted server splits lines across chunks and cuts
+//! the transfer short, which wiremock can't.
+mod support;
+
+#[cfg(test)]
+mod tests {
+    use crate::support::{split_every, ScriptedResponse, ScriptedServer};
+    use futures::StreamExt;
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        id: u32,
+        kind: String,
+    }
+
+    http_provider!(
+        EventProvider,
+        behavior_version: 1,
+        {
+            { path: "/events", method: GET, fn_name: events, res: Event, stream: ndjson },
+            { path: "/events", method: GET, fn_name: events_if_any, res: Event, stream: ndjson, not_found_as_none: true },
+        }
+    );
+
+    http_provider!(
+        EventV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/events", method: GET, fn_name: events, res: Event, stream: ndjson },
+        }
+    );
+
+    fn event(id: u32) -> Event {
+        Event {
+            id,
+            kind: format!("kind-{}", id),
+        }
+    }
+
+    /// Three events, with a blank keep-alive line, CRLF line endings and no final newline.
+    fn body() -> String {
+        format!(
+            "{}\n\n{}\r\n{}",
+            serde_json::to_string(&event(1)).unwrap(),
+            serde_json::to_string(&event(2)).unwrap(),
+            serde_json::to_string(&event(3)).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_lines_are_streamed_as_items() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .and(header("accept", "application/x-ndjson"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body(), "application/x-ndjson"))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = EventProvider::new(url, Duration::from_secs(5));
+
+        let events: Vec<_> = provider.events().await?.collect().await;
+        assert_eq!(events, vec![Ok(event(1)), Ok(event(2)), Ok(event(3))]);
+
+        let events = provider.events_if_any().await?.expect("events were found");
+        assert_eq!(events.count().await, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lines_split_across_chunks_are_joined() -> Result<(), Box<dyn std::error::Error>> {
+        let script = ScriptedResponse::new(200)
+            .chunked_body(split_every(body().as_bytes(), 7))
+            .into_script();
+        let server = ScriptedServer::start(script).await;
+        let provider = EventV2Provider::new(server.url(), Duration::from_secs(5));
+
+        let mut events = provider.events().await?;
+        for id in 1..=3 {
+            assert_eq!(events.next().await.transpose()?, Some(event(id)));
+        }
+        assert!(events.next().await.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_malformed_lines_fail_alone() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let body = format!(
+            "{}\n{{\"id\":\"two\"}}\n{}\n",
+            serde_json::to_string(&event(1))?,
+            serde_json::to_string(&event(3))?,
+        );
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+
+        let provider = EventProvider::new(url.clone(), Duration::from_secs(5));
+        let events: Vec<_> = provider.events().await?.collect().await;
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0], Ok(event(1)));
+        let error = events[1].as_ref().unwrap_err();
+        assert!(
+            error.starts_with("Failed to deserialize response: "),
+            "{}",
+            error
+        );
+        assert_eq!(events[2], Ok(event(3)));
+
+        let provider = EventV2Provider::new(url, Duration::from_secs(5));
+        let events: Vec<_> = provider.events().await?.collect().await;
+        match &events[1] {
+            Err(EventV2ProviderError::SchemaMismatch { body, .. }) => {
+                assert_eq!(body, "{\"id\":\"two\"}")
+            }
+            other => panic!("unexpected item: {:?}", other),
+        }
+        assert!(matches!(events[2], Ok(ref item) if *item == event(3)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_transfer_ends_the_stream() -> Result<(), Box<dyn std::error::Error>> {
+        let line = format!("{}\n", serde_json::to_string(&event(1))?);
+        let body = format!("{}{}", line, line);
+        let script = ScriptedResponse::new(200)
+            .chunked_body(split_every(body.as_bytes(), line.len()))
+            .reset_after(line.len() + 5)
+            .into_script();
+        let server = ScriptedServer::start(script).await;
+        let provider = EventV2Provider::new(server.url(), Duration::from_secs(5));
+
+        let events: Vec<_> = provider.events().await?.collect().await;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], Ok(ref item) if *item == event(1)));
+        assert!(
+            matches!(events[1], Err(EventV2ProviderError::BodyTransfer { .. })),
+            "{:?}",
+            events[1]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+//! Endpoints declaring `res: ()` check the status and skip deserialization, so 204 No Content
+//! and empty 200 responses succeed.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Serialize;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct ItemPath {
+        id: u32,
+    }
+
+    http_provider!(
+        ItemProvider,
+        behavior_version: 1,
+        {
+            { path: "/items/{id}", method: DELETE, fn_name: delete, path_params: ItemPath, res: () },
+            { path: "/items/{id}", method: DELETE, fn_name: delete_raw, path_params: ItemPath, res: (), keep_raw_body: true },
+        }
+    );
+
+    http_provider!(
+        ItemV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/items/{id}", method: DELETE, fn_name: delete, path_params: ItemPath, res: () },
+            { path: "/items/{id}", method: DELETE, fn_name: delete_missing, path_params: ItemPath, res: (), not_found_as_none: true },
+        }
+    );
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        let responses = [
+            ("/items/1", ResponseTemplate::new(204)),
+            ("/items/2", ResponseTemplate::new(200)),
+            (
+                "/items/3",
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "deleted": true })),
+            ),
+            (
+                "/items/4",
+                ResponseTemplate::new(404).set_body_string("no such item"),
+            ),
+        ];
+        for (item, response) in responses {
+            Mock::given(method("DELETE"))
+                .and(path(item))
+                .respond_with(response)
+                .mount(&mock_server)
+                .await;
+        }
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_unit_response_skips_deserialization() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ItemProvider::new(url.clone(), Duration::from_secs(5));
+        let v2_provider = ItemV2Provider::new(url, Duration::from_secs(5));
+
+        // No Content, an empty 200 and a body nobody asked for all succeed
+        for id in [1, 2, 3] {
+            provider.delete(&ItemPath { id }).await?;
+            v2_provider.delete(&ItemPath { id }).await?;
+        }
+
+        // The status is still checked
+        assert_eq!(
+            provider.delete(&ItemPath { id: 4 }).await,
+            Err("HTTP request failed with status 404: Not Found: no such item".to_string())
+        );
+        match v2_provider.delete(&ItemPath { id: 4 }).await.unwrap_err() {
+            ItemV2ProviderError::Status { code, .. } => assert_eq!(code, 404),
+            error => panic!("unexpected error: {:?}", error),
+        }
+        assert_eq!(v2_provider.delete_missing(&ItemPath { id: 4 }).await?, None);
+        assert_eq!(
+            v2_provider.delete_missing(&ItemPath { id: 1 }).await?,
+            Some(())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unit_response_keeps_raw_body() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            ItemProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let ((), raw_body) = provider.delete_raw(&ItemPath { id: 1 }).await?;
+        assert!(raw_body.is_empty());
+        let ((), raw_body) = provider.delete_raw(&ItemPath { id: 3 }).await?;
+        assert_eq!(raw_body.as_ref(), br#"{"deleted":true}"#);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+//! `not_found_as_none` endpoints return `Ok(None)` for a 404 and fail on other error statuses.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct User {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct UserPath {
+        id: u32,
+    }
+
+    http_provider!(
+        LookupProvider,
+        behavior_version: 1,
+        {
+            { path: "/users/{id}", method: GET, fn_name: find, path_params: UserPath, res: User, not_found_as_none: true },
+            { path: "/users/{id}", method: GET, fn_name: find_raw, path_params: UserPath, res: User, not_found_as_none: true, keep_raw_body: true },
+            { path: "/users/{id}", method: GET, fn_name: find_emitted, path_params: UserPath, res: User, not_found_as_none: true, emit: Counter },
+            { path: "/users/{id}", method: HEAD, fn_name: exists, path_params: UserPath, not_found_as_none: true },
+            { path: "/users/{id}", method: GET, fn_name: get, path_params: UserPath, res: User },
+        }
+    );
+
+    http_provider!(
+        LookupV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/users/{id}", method: GET, fn_name: find, path_params: UserPath, res: User, not_found_as_none: true },
+        }
+    );
+
+    struct Counter(Arc<AtomicUsize>);
+
+    impl LookupProviderResponseSink<User> for Counter {
+        fn accept(&self, _: &User) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 1 })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/users/1"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/users/2"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("no such user"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/users/3"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_not_found_is_none() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            LookupProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(
+            provider.find(&UserPath { id: 1 }).await?,
+            Some(User { id: 1 })
+        );
+        assert_eq!(provider.find(&UserPath { id: 2 }).await?, None);
+        assert_eq!(
+            provider.find(&UserPath { id: 3 }).await,
+            Err("HTTP request failed with status 500: Internal Server Error".to_string())
+        );
+
+        let (user, raw_body) = provider.find_raw(&UserPath { id: 1 }).await?.unwrap();
+        assert_eq!(
+            (user, raw_body.as_ref()),
+            (User { id: 1 }, br#"{"id":1}"#.as_ref())
+        );
+        assert!(provider.find_raw(&UserPath { id: 2 }).await?.is_none());
+
+        assert!(provider.exists(&UserPath { id: 1 }).await?.is_some());
+        assert!(provider.exists(&UserPath { id: 2 }).await?.is_none());
+
+        // Endpoints without the option still fail on a 404
+        assert_eq!(
+            provider.get(&UserPath { id: 2 }).await,
+            Err("HTTP request failed with status 404: Not Found: no such user".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_not_found_is_not_emitted_or_failed() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let provider =
+            LookupProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5))
+                .with_sink(Counter(accepted.clone()));
+
+        provider.find_emitted(&UserPath { id: 1 }).await?;
+        provider.find_emitted(&UserPath { id: 2 }).await?;
+        assert_eq!(accepted.load(Ordering::Relaxed), 1);
+        assert_eq!(provider.stats().requests_failed, 0);
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = LookupV2Provider::new(url, Duration::from_secs(5));
+        assert_eq!(provider.find(&UserPath { id: 2 }).await?, None);
+        match provider.find(&UserPath { id: 3 }).await.unwrap_err() {
+            LookupV2ProviderError::Status { code, .. } => assert_eq!(code, 500),
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        Ok(())
+    }
+}
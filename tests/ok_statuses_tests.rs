@@ -0,0 +1,94 @@
+//! `ok_statuses` endpoints deserialize the listed statuses into `res`, and handle any other
+//! status, even a 2xx, as an error.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Document {
+        version: u32,
+        complete: bool,
+    }
+
+    http_provider!(
+        DocumentProvider,
+        behavior_version: 1,
+        {
+            { path: "/document", method: GET, fn_name: fetch, res: Document, ok_statuses: [200, 206, 409] },
+        }
+    );
+
+    http_provider!(
+        DocumentV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/document", method: GET, fn_name: fetch, res: Document, ok_statuses: [200, 206, 409] },
+        }
+    );
+
+    async fn server_answering(status: u16, complete: bool) -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/document"))
+            .respond_with(ResponseTemplate::new(status).set_body_json(Document {
+                version: 3,
+                complete,
+            }))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_listed_statuses_are_deserialized() -> Result<(), Box<dyn std::error::Error>> {
+        for (status, complete) in [(200, true), (206, false), (409, true)] {
+            let mock_server = server_answering(status, complete).await;
+            let url = Url::from_str(&mock_server.uri())?;
+            let expected = Document {
+                version: 3,
+                complete,
+            };
+
+            let provider = DocumentProvider::new(url.clone(), Duration::from_secs(5));
+            assert_eq!(provider.fetch().await?, expected);
+            let provider = DocumentV2Provider::new(url, Duration::from_secs(5));
+            assert_eq!(provider.fetch().await?, expected);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_other_statuses_are_errors() -> Result<(), Box<dyn std::error::Error>> {
+        let body = r#"{"version":3,"complete":true}"#;
+        for status in [201, 404] {
+            let mock_server = server_answering(status, true).await;
+            let url = Url::from_str(&mock_server.uri())?;
+
+            let provider = DocumentProvider::new(url.clone(), Duration::from_secs(5));
+            let error = provider.fetch().await.unwrap_err();
+            assert!(
+                error.starts_with(&format!("HTTP request failed with status {}", status)),
+                "{}",
+                error
+            );
+            let provider = DocumentV2Provider::new(url, Duration::from_secs(5));
+            match provider.fetch().await.unwrap_err() {
+                DocumentV2ProviderError::Status { code, body: error } => {
+                    assert_eq!((code, error.as_str()), (status, body))
+                }
+                error => panic!("unexpected error: {:?}", error),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+//! `optimistic_lock` endpoints send the caller's `ETag` as `If-Match`, and return a
+//! `412 Precondition Failed` as a `Conflict` outcome instead of an error.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, Url};
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Document {
+        title: String,
+    }
+
+    #[derive(Serialize)]
+    struct DocumentPath {
+        id: u32,
+    }
+
+    http_provider!(
+        DocumentProvider,
+        behavior_version: 1,
+        {
+            { path: "/documents/{id}", method: PUT, fn_name: update, path_params: DocumentPath, req: Document, res: Document, headers: HeaderMap, optimistic_lock: true },
+            { path: "/documents/{id}", method: DELETE, fn_name: delete, path_params: DocumentPath, res: (), optimistic_lock: true, not_found_as_none: true },
+        }
+    );
+
+    http_provider!(
+        DocumentV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/documents/{id}", method: PATCH, fn_name: update, path_params: DocumentPath, req: Document, res: Document, optimistic_lock: true },
+        }
+    );
+
+    fn document() -> Document {
+        Document {
+            title: "draft".to_string(),
+        }
+    }
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(path("/documents/1"))
+            .and(header("if-match", "\"v2\""))
+            .respond_with(ResponseTemplate::new(200).set_body_json(document()))
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/documents/1"))
+            .and(header("if-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(412).set_body_string("stale"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/documents/2"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/documents/3"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_precondition_outcomes() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = DocumentProvider::new(url.clone(), Duration::from_secs(5));
+        let item = DocumentPath { id: 1 };
+
+        assert_eq!(
+            provider
+                .update(&item, &document(), HeaderMap::new(), "\"v2\"")
+                .await?,
+            DocumentProviderUpdateOutcome::Applied(document())
+        );
+        assert_eq!(
+            provider
+                .update(&item, &document(), HeaderMap::new(), "\"v1\"")
+                .await?,
+            DocumentProviderUpdateOutcome::Conflict
+        );
+
+        // The `etag` argument replaces an `If-Match` passed in the headers
+        let mut headers = HeaderMap::new();
+        headers.insert("if-match", "\"v1\"".parse()?);
+        assert_eq!(
+            provider
+                .update(&item, &document(), headers, "\"v2\"")
+                .await?,
+            DocumentProviderUpdateOutcome::Applied(document())
+        );
+
+        assert_eq!(
+            provider.delete(&item, "\"v1\"").await?,
+            Some(DocumentProviderUpdateOutcome::Conflict)
+        );
+        assert_eq!(
+            provider.delete(&item, "\"v2\"").await?,
+            Some(DocumentProviderUpdateOutcome::Applied(()))
+        );
+        assert_eq!(
+            provider.delete(&DocumentPath { id: 2 }, "\"v1\"").await?,
+            None
+        );
+
+        // Other error statuses still fail the call
+        let provider = DocumentV2Provider::new(url, Duration::from_secs(5));
+        assert_eq!(
+            provider.update(&item, &document(), "\"v1\"").await?,
+            DocumentV2ProviderUpdateOutcome::Conflict
+        );
+        match provider
+            .update(&DocumentPath { id: 3 }, &document(), "\"v1\"")
+            .await
+            .unwrap_err()
+        {
+            DocumentV2ProviderError::Status { code, .. } => assert_eq!(code, 500),
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_etag_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = DocumentProvider::new(url.clone(), Duration::from_secs(5));
+        let item = DocumentPath { id: 1 };
+
+        assert_eq!(
+            provider
+                .update(&item, &document(), HeaderMap::new(), "")
+                .await,
+            Err("`etag` is empty: pass the `ETag` of the version being changed".to_string())
+        );
+        assert_eq!(
+            provider
+                .update(
+                    &item,
+                    &document(),
+                    HeaderMap::new(),
+                    "\"v1\"\r\nx-injected: 1"
+                )
+                .await,
+            Err(r#"`etag` is not a valid header value: "\"v1\"\r\nx-injected: 1""#.to_string())
+        );
+        let provider = DocumentV2Provider::new(url, Duration::from_secs(5));
+        let error = provider.update(&item, &document(), "").await.unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`etag` is empty: pass the `ETag` of the version being changed"
+        );
+        match provider
+            .update(&item, &document(), "\"v1\"\r\nx-injected: 1")
+            .await
+            .unwrap_err()
+        {
+            DocumentV2ProviderError::InvalidHeader {
+                name: "if-match",
+                value: Some(value),
+                ..
+            } => assert_eq!(value, "\"v1\"\r\nx-injected: 1"),
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        // Nothing was sent without a precondition
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+
+        Ok(())
+    }
+}
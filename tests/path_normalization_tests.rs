@@ -0,0 +1,190 @@
+//! Repeated slashes from base URLs, group prefixes, endpoint paths and empty path parameters
+//! are collapsed under `behavior_version: 2`, identically in requests and path metadata.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Serialize;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{matchers::any, Mock, MockServer, ResponseTemplate};
+
+    #[derive(Serialize)]
+    struct UserPath {
+        id: String,
+    }
+
+    http_provider!(
+        SlashProvider,
+        behavior_version: 2,
+        {
+            { path: "/users", method: GET, fn_name: plain, res: u32 },
+            { path: "//users//", method: GET, fn_name: doubled, res: u32 },
+            { path: "/users//{id}/posts", method: GET, fn_name: posts, path_params: UserPath, res: u32 },
+            { path: "/users//raw", method: GET, fn_name: raw, res: u32, preserve_path: true },
+            v2: {
+                prefix: "/v2/",
+                { path: "/users", method: GET, fn_name: users, res: u32 },
+                { path: "//users", method: GET, fn_name: doubled, res: u32 },
+                nested: {
+                    prefix: "//admin//",
+                    { path: "users", method: GET, fn_name: users, res: u32 },
+                },
+            },
+        }
+    );
+
+    http_provider!(
+        LegacySlashProvider,
+        behavior_version: 1,
+        {
+            { path: "/users//raw", method: GET, fn_name: raw, res: u32 },
+        }
+    );
+
+    async fn requested_path(
+        mock_server: &MockServer,
+        call: impl std::future::Future<Output = Result<u32, String>>,
+    ) -> String {
+        call.await.unwrap();
+        let requests = mock_server.received_requests().await.unwrap();
+        requests.last().unwrap().url.path().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_request_paths_are_normalized() {
+        let mock_server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(1))
+            .mount(&mock_server)
+            .await;
+
+        for (base_path, base_expected) in [
+            ("", ""),
+            ("/", ""),
+            ("/api", "/api"),
+            ("/api/", "/api"),
+            ("//api//", "/api"),
+        ] {
+            let url = Url::from_str(&format!("{}{}", mock_server.uri(), base_path)).unwrap();
+            let provider = SlashProvider::new(url, Duration::from_secs(5));
+            let empty = UserPath { id: String::new() };
+            let seven = UserPath {
+                id: "7".to_string(),
+            };
+
+            let cases = [
+                (
+                    requested_path(&mock_server, async {
+                        provider.plain().await.map_err(|e| e.to_string())
+                    })
+                    .await,
+                    "/users",
+                ),
+                (
+                    requested_path(&mock_server, async {
+                        provider.doubled().await.map_err(|e| e.to_string())
+                    })
+                    .await,
+                    "/users/",
+                ),
+                (
+                    requested_path(&mock_server, async {
+                        provider.posts(&seven).await.map_err(|e| e.to_string())
+                    })
+                    .await,
+                    "/users/7/posts",
+                ),
+                (
+                    requested_path(&mock_server, async {
+                        provider.posts(&empty).await.map_err(|e| e.to_string())
+                    })
+                    .await,
+                    "/users/posts",
+                ),
+                (
+                    requested_path(&mock_server, async {
+                        provider.v2().users().await.map_err(|e| e.to_string())
+                    })
+                    .await,
+                    "/v2/users",
+                ),
+                (
+                    requested_path(&mock_server, async {
+                        provider.v2().doubled().await.map_err(|e| e.to_string())
+                    })
+                    .await,
+                    "/v2/users",
+                ),
+                (
+                    requested_path(&mock_server, async {
+                        provider
+                            .v2()
+                            .nested()
+                            .users()
+                            .await
+                            .map_err(|e| e.to_string())
+                    })
+                    .await,
+                    "/v2/admin/users",
+                ),
+            ];
+            for (requested, expected) in cases {
+                assert_eq!(
+                    requested,
+                    format!("{}{}", base_expected, expected),
+                    "base {:?}",
+                    base_path
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preserve_path_keeps_slashes() {
+        let mock_server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(1))
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&format!("{}/api", mock_server.uri())).unwrap();
+        let provider = SlashProvider::new(url, Duration::from_secs(5));
+        let requested = requested_path(&mock_server, async {
+            provider.raw().await.map_err(|e| e.to_string())
+        })
+        .await;
+        assert_eq!(requested, "/api/users//raw");
+
+        // Version 1 keeps every path as written
+        let url = Url::from_str(&mock_server.uri()).unwrap();
+        let provider = LegacySlashProvider::new(url, Duration::from_secs(5));
+        assert_eq!(
+            requested_path(&mock_server, provider.raw()).await,
+            "/users//raw"
+        );
+    }
+
+    #[test]
+    fn test_path_metadata_is_normalized() {
+        let templates = [
+            (SlashProvider::PLAIN_PATH_TEMPLATE, "/users"),
+            (SlashProvider::DOUBLED_PATH_TEMPLATE, "/users/"),
+            (SlashProvider::POSTS_PATH_TEMPLATE, "/users/{id}/posts"),
+            (SlashProvider::RAW_PATH_TEMPLATE, "/users//raw"),
+            (SlashProviderV2::USERS_PATH_TEMPLATE, "/v2/users"),
+            (SlashProviderV2::DOUBLED_PATH_TEMPLATE, "/v2/users"),
+            (
+                SlashProviderV2Nested::USERS_PATH_TEMPLATE,
+                "/v2/admin/users",
+            ),
+            (LegacySlashProvider::RAW_PATH_TEMPLATE, "/users//raw"),
+        ];
+        for (template, expected) in templates {
+            assert_eq!(template, expected);
+        }
+
+        assert_eq!(SlashProvider::posts_path_regex(), "^/users/[^/]+/posts$");
+        assert_eq!(SlashProviderV2::doubled_path_regex(), "^/v2/users$");
+    }
+}
@@ -0,0 +1,296 @@
+//! `follow_presigned` endpoints follow the API's redirect to a presigned URL by hand, fetching
+//! it without any of the headers the provider adds, and return the downloaded bytes, write
+//! them to a `download` writer, or decode them as `stream` items.
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, Url};
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct FilePath {
+        id: u32,
+    }
+
+    http_provider!(
+        DownloadProvider,
+        behavior_version: 2,
+        api_version: "2024-01-01",
+        {
+            { path: "/files/{id}/download", method: GET, fn_name: download, path_params: FilePath, headers: HeaderMap, follow_presigned: true },
+            { path: "/files/{id}/download", method: GET, fn_name: find, path_params: FilePath, follow_presigned: true, not_found_as_none: true },
+            { path: "/files/{id}/download", method: GET, fn_name: save, path_params: FilePath, follow_presigned: true, download: true },
+            { path: "/files/{id}/download", method: GET, fn_name: records, path_params: FilePath, res: Record, follow_presigned: true, stream: ndjson },
+        }
+    );
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Record {
+        id: u32,
+    }
+
+    http_provider!(
+        LegacyDownloadProvider,
+        behavior_version: 1,
+        {
+            { path: "/files/{id}/download", method: GET, fn_name: download, path_params: FilePath, follow_presigned: true },
+        }
+    );
+
+    const CONTENT: &[u8] = b"\x00\x01binary file content\xff";
+
+    /// Starts an API redirecting `/files/1/download` to `location`.
+    async fn api_server(location: &str) -> MockServer {
+        let api = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/files/1/download"))
+            .and(header("authorization", "Bearer api-token"))
+            .respond_with(ResponseTemplate::new(307).insert_header("location", location))
+            .mount(&api)
+            .await;
+        api
+    }
+
+    /// Starts a storage host serving the file at `/bucket/file`.
+    async fn storage_server() -> MockServer {
+        let storage = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/file"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(CONTENT))
+            .mount(&storage)
+            .await;
+        storage
+    }
+
+    fn provider(api: &MockServer) -> DownloadProvider {
+        let mut url = Url::from_str(&api.uri()).unwrap();
+        url.set_username("user").unwrap();
+        url.set_password(Some("secret")).unwrap();
+        DownloadProvider::new(url, Duration::from_secs(5))
+            .with_header_context(|| {
+                let mut headers = HeaderMap::new();
+                headers.insert("authorization", "Bearer api-token".parse().unwrap());
+                headers.insert("x-tenant", "acme".parse().unwrap());
+                Some(headers)
+            })
+            .with_request_signer(|request| {
+                request
+                    .headers_mut()
+                    .insert("x-signature", "signed".parse().unwrap());
+                Ok(())
+            })
+    }
+
+    fn header_names(request: &Request) -> Vec<String> {
+        request
+            .headers
+            .keys()
+            .map(|name| name.as_str().to_ascii_lowercase())
+            .collect()
+    }
+
+    /// Asserts the presigned host received exactly one request, without the provider's headers.
+    async fn assert_clean_presigned_request(storage: &MockServer) {
+        let storage_requests = storage.received_requests().await.unwrap();
+        assert_eq!(storage_requests.len(), 1);
+        let storage_headers = header_names(&storage_requests[0]);
+        for name in ["authorization", "x-tenant", "x-signature", "x-api-version"] {
+            assert!(
+                !storage_headers.iter().any(|header| header == name),
+                "presigned host received `{}`: {:?}",
+                name,
+                storage_headers
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_presigned_host_gets_no_provider_headers() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let storage = storage_server().await;
+        let presigned = format!("{}/bucket/file?X-Amz-Signature=abc", storage.uri());
+        let api = api_server(&presigned).await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", "42".parse()?);
+        let body = provider(&api)
+            .download(&FilePath { id: 1 }, headers)
+            .await?;
+        assert_eq!(body.as_ref(), CONTENT);
+
+        // The API saw every header the provider adds
+        let api_requests = api.received_requests().await.unwrap();
+        assert_eq!(api_requests.len(), 1);
+        let api_headers = header_names(&api_requests[0]);
+        for name in [
+            "authorization",
+            "x-tenant",
+            "x-signature",
+            "x-api-version",
+            "x-request-id",
+        ] {
+            assert!(api_headers.iter().any(|header| header == name), "{}", name);
+        }
+
+        // The presigned host saw none of them, only its own query signature
+        let storage_requests = storage.received_requests().await.unwrap();
+        assert_eq!(storage_requests.len(), 1);
+        assert_eq!(storage_requests[0].url.query(), Some("X-Amz-Signature=abc"));
+        let storage_headers = header_names(&storage_requests[0]);
+        for name in [
+            "authorization",
+            "x-tenant",
+            "x-signature",
+            "x-api-version",
+            "x-request-id",
+        ] {
+            assert!(
+                !storage_headers.iter().any(|header| header == name),
+                "presigned host received `{}`: {:?}",
+                name,
+                storage_headers
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_relative_location_is_resolved() -> Result<(), Box<dyn std::error::Error>> {
+        let api = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/files/1/download"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/bucket/file"))
+            .mount(&api)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/file"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(CONTENT))
+            .mount(&api)
+            .await;
+
+        let mut url = Url::from_str(&api.uri())?;
+        url.set_username("user").unwrap();
+        url.set_password(Some("secret")).unwrap();
+        let provider = LegacyDownloadProvider::new(url, Duration::from_secs(5));
+        assert_eq!(
+            provider.download(&FilePath { id: 1 }).await?.as_ref(),
+            CONTENT
+        );
+
+        // The second request to the same host still leaves out the basic auth credentials
+        let requests = api.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let has_authorization = |request: &Request| {
+            header_names(request)
+                .iter()
+                .any(|header| header == "authorization")
+        };
+        assert!(has_authorization(&requests[0]));
+        assert!(!has_authorization(&requests[1]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unexpected_responses_fail() -> Result<(), Box<dyn std::error::Error>> {
+        let storage = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/file"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("SignatureDoesNotMatch"))
+            .mount(&storage)
+            .await;
+        let api = api_server(&format!("{}/bucket/file", storage.uri())).await;
+        Mock::given(method("GET"))
+            .and(path("/files/2/download"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+            .mount(&api)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/files/3/download"))
+            .respond_with(ResponseTemplate::new(303))
+            .mount(&api)
+            .await;
+        let provider = provider(&api);
+
+        // A rejected presigned request is reported like any error response
+        match provider
+            .download(&FilePath { id: 1 }, HeaderMap::new())
+            .await
+            .unwrap_err()
+        {
+            DownloadProviderError::Status { code, body } => {
+                assert_eq!((code, body.as_str()), (403, "SignatureDoesNotMatch"))
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        let error = provider
+            .download(&FilePath { id: 2 }, HeaderMap::new())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Expected a redirect to a presigned URL, got status 200"
+        );
+
+        let error = provider
+            .download(&FilePath { id: 3 }, HeaderMap::new())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Redirect with status 303 has no valid `Location` header"
+        );
+
+        // A missing file is still a 404 from the API, before any redirect
+        assert_eq!(provider.find(&FilePath { id: 4 }).await?, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_presigned_body_is_downloaded() -> Result<(), Box<dyn std::error::Error>> {
+        let storage = storage_server().await;
+        let api = api_server(&format!("{}/bucket/file", storage.uri())).await;
+
+        let mut written = Vec::new();
+        let count = provider(&api)
+            .save(&FilePath { id: 1 }, &mut written)
+            .await?;
+        assert_eq!(count, CONTENT.len() as u64);
+        assert_eq!(written, CONTENT);
+        assert_clean_presigned_request(&storage).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_presigned_body_is_streamed() -> Result<(), Box<dyn std::error::Error>> {
+        let storage = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/records"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{\"id\":1}\n{\"id\":2}\n"))
+            .mount(&storage)
+            .await;
+        let api = api_server(&format!("{}/bucket/records", storage.uri())).await;
+
+        let records: Vec<Record> = provider(&api)
+            .records(&FilePath { id: 1 })
+            .await?
+            .map(|record| record.unwrap())
+            .collect()
+            .await;
+        assert_eq!(records, vec![Record { id: 1 }, Record { id: 2 }]);
+        assert_clean_presigned_request(&storage).await;
+
+        Ok(())
+    }
+}
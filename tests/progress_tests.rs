@@ -0,0 +1,141 @@
+//! `progress` endpoints report the bytes of a `download` or `upload_stream` body transferred
+//! so far to a callback, which doesn't have to be `Send` or `Sync`.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_bytes, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Upload {
+        size: u64,
+    }
+
+    http_provider!(
+        TransferProvider,
+        behavior_version: 1,
+        {
+            { path: "/artifact", method: GET, fn_name: download, download: true, progress: true },
+            { path: "/uploads", method: PUT, fn_name: upload, upload_stream: true, res: Upload, progress: true },
+            { path: "/uploads", method: PUT, fn_name: upload_args, upload_stream: true, res: Upload, progress: true, args_struct: true },
+        }
+    );
+
+    fn payload() -> Vec<u8> {
+        (0..50_000u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn payload_chunks() -> Vec<std::io::Result<bytes::Bytes>> {
+        payload()
+            .chunks(4096)
+            .map(|chunk| Ok(bytes::Bytes::copy_from_slice(chunk)))
+            .collect()
+    }
+
+    type Calls = Rc<RefCell<Vec<(u64, Option<u64>)>>>;
+
+    /// A callback recording its calls, deliberately neither `Send` nor `Sync`.
+    fn recorder() -> (Calls, impl Fn(u64, Option<u64>)) {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let recorded = calls.clone();
+        (calls, move |sent, total| {
+            recorded.borrow_mut().push((sent, total))
+        })
+    }
+
+    fn assert_progress(calls: &[(u64, Option<u64>)], total: Option<u64>) {
+        let size = payload().len() as u64;
+        assert!(calls.len() >= 2, "{:?}", calls);
+        assert!(
+            calls.windows(2).all(|pair| pair[0].0 <= pair[1].0),
+            "{:?}",
+            calls
+        );
+        assert!(calls.iter().all(|(_, reported)| *reported == total));
+        assert_eq!(calls.last(), Some(&(size, total)));
+    }
+
+    #[tokio::test]
+    async fn test_download_progress_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/artifact"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(payload()))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = TransferProvider::new(url, Duration::from_secs(5));
+
+        let (calls, progress) = recorder();
+        let mut written = Vec::new();
+        let count = provider.download(&mut written, progress).await?;
+        assert_eq!(count, payload().len() as u64);
+        assert_eq!(written, payload());
+        assert_progress(&calls.borrow(), Some(payload().len() as u64));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upload_progress_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let size = payload().len() as u64;
+        Mock::given(method("PUT"))
+            .and(path("/uploads"))
+            .and(header("content-length", size.to_string().as_str()))
+            .and(body_bytes(payload()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Upload { size }))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = TransferProvider::new(url, Duration::from_secs(5));
+
+        let (calls, progress) = recorder();
+        let stream = futures::stream::iter(payload_chunks());
+        let upload = provider.upload(stream, Some(size), progress).await?;
+        assert_eq!(upload, Upload { size });
+        assert_progress(&calls.borrow(), Some(size));
+
+        let (calls, progress) = recorder();
+        let upload = provider
+            .upload_args(
+                TransferProviderUploadArgsArgs {
+                    body: Box::pin(futures::stream::iter(payload_chunks())),
+                    content_length: Some(size),
+                },
+                progress,
+            )
+            .await?;
+        assert_eq!(upload, Upload { size });
+        assert_progress(&calls.borrow(), Some(size));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty_bodies_report_completion() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/artifact"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = TransferProvider::new(url, Duration::from_secs(5));
+
+        let (calls, progress) = recorder();
+        let mut written = Vec::new();
+        assert_eq!(provider.download(&mut written, progress).await?, 0);
+        assert_eq!(*calls.borrow(), vec![(0, Some(0))]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+//! `codec: protobuf` endpoints send and decode `prost` messages, which don't implement the
+//! serde traits. Run with `cargo test --features protobuf`.
+#![cfg(feature = "protobuf")]
+
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use prost::Message;
+    use reqwest::Url;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, Request, Respond, ResponseTemplate,
+    };
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Sample {
+        #[prost(uint64, tag = "1")]
+        id: u64,
+        #[prost(string, tag = "2")]
+        label: String,
+        #[prost(float, repeated, tag = "3")]
+        readings: Vec<f32>,
+    }
+
+    http_provider!(
+        SampleProvider,
+        behavior_version: 1,
+        {
+            { path: "/echo", method: POST, fn_name: echo, req: Sample, res: Sample, codec: protobuf },
+            { path: "/latest", method: GET, fn_name: latest, res: Sample, codec: protobuf, keep_raw_body: true },
+            { path: "/latest", method: GET, fn_name: latest_off_thread, res: Sample, codec: protobuf, deserialize_off_thread: true },
+        }
+    );
+
+    http_provider!(
+        SampleV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/echo", method: POST, fn_name: echo, req: Sample, res: Sample, codec: protobuf },
+            { path: "/latest", method: GET, fn_name: latest, res: Sample, codec: protobuf },
+        }
+    );
+
+    /// Answers with the request body, as protobuf.
+    struct Echo;
+
+    impl Respond for Echo {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            ResponseTemplate::new(200).set_body_raw(request.body.clone(), "application/x-protobuf")
+        }
+    }
+
+    fn sample() -> Sample {
+        Sample {
+            id: 7,
+            label: "north".to_string(),
+            readings: vec![1.5, -2.25],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bodies_round_trip_as_protobuf() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/echo"))
+            .and(header("content-type", "application/x-protobuf"))
+            .and(header("accept", "application/x-protobuf"))
+            .respond_with(Echo)
+            .mount(&mock_server)
+            .await;
+        let encoded = sample().encode_to_vec();
+        Mock::given(method("GET"))
+            .and(path("/latest"))
+            .and(header("accept", "application/x-protobuf"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(encoded.clone(), "application/x-protobuf"),
+            )
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+
+        let provider = SampleProvider::new(url.clone(), Duration::from_secs(5));
+        assert_eq!(provider.echo(&sample()).await?, sample());
+        let (latest, raw_body) = provider.latest().await?;
+        assert_eq!(latest, sample());
+        assert_eq!(raw_body.as_ref(), encoded.as_slice());
+        assert_eq!(provider.latest_off_thread().await?, sample());
+
+        let provider = SampleV2Provider::new(url, Duration::from_secs(5));
+        assert_eq!(provider.echo(&sample()).await?, sample());
+        assert_eq!(provider.latest().await?, sample());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_undecodable_bodies_fail() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        // A length-delimited field running past the end of the body
+        Mock::given(method("GET"))
+            .and(path("/latest"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(vec![0x12, 0x10, b'n'], "application/x-protobuf"),
+            )
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+
+        let provider = SampleProvider::new(url.clone(), Duration::from_secs(5));
+        let error = provider.latest().await.unwrap_err();
+        assert!(
+            error.starts_with("Failed to deserialize response: "),
+            "{}",
+            error
+        );
+
+        let provider = SampleV2Provider::new(url, Duration::from_secs(5));
+        match provider.latest().await.unwrap_err() {
+            SampleV2ProviderError::Other(message) => {
+                assert!(message.starts_with("Failed to deserialize response: "))
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        Ok(())
+    }
+}
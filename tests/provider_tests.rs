@@ -5,11 +5,13 @@ mod tests {
     use reqwest::{header::HeaderMap, Url};
     use serde::{Deserialize, Serialize};
     use std::str::FromStr;
+    use std::time::Duration;
     use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
 
     // Define the provider and its methods using the macro
     http_provider!(
         HttpProvider,
+        behavior_version: 1,
         {
             {
                 path: "/custom-path",
@@ -52,6 +54,40 @@ mod tests {
                 fn_name: get_user,
                 res: garden::api::primitives::Response<MyResponse>,
             },
+            {
+                path: "/sessions",
+                method: DELETE,
+                fn_name: delete_sessions,
+                res: garden::api::primitives::Response<MyResponse>,
+                headers: reqwest::header::HeaderMap,
+                query_params: SessionQuery,
+            },
+            {
+                path: "/users/{id}",
+                method: PATCH,
+                path_params: MyPathParams,
+                req: MyRequest,
+                res: garden::api::primitives::Response<MyResponse>,
+            },
+            {
+                path: "/objects/{id}",
+                method: HEAD,
+                path_params: MyPathParams,
+            },
+            {
+                path: "/users",
+                method: OPTIONS,
+            },
+            {
+                path: "/capabilities",
+                method: OPTIONS,
+                res: Vec<String>,
+            },
+            {
+                path: "/calendars/work",
+                method: "REPORT",
+                res: Vec<String>,
+            },
         }
 
     );
@@ -66,6 +102,11 @@ mod tests {
         query: String,
     }
 
+    #[derive(Serialize, Deserialize)]
+    struct SessionQuery {
+        user_id: String,
+    }
+
     // Define the request and response types
     #[derive(Serialize, Deserialize)]
     struct MyRequest {
@@ -102,7 +143,7 @@ mod tests {
             .await;
 
         let url = Url::from_str(&mock_server.uri())?;
-        let provider = HttpProvider::new(url, Some(5000));
+        let provider = HttpProvider::new(url, Duration::from_secs(5));
 
         // Create headers with a custom value
         let mut headers = HeaderMap::new();
@@ -114,10 +155,10 @@ mod tests {
                 &MyRequest {
                     query: "Helo".to_string(),
                 },
-                headers,
                 &MyQueryParams {
                     query: "Helo".to_string(),
                 },
+                headers,
             )
             .await?;
 
@@ -153,14 +194,14 @@ mod tests {
 
         // Set up a mock that matches the dynamic path
         Mock::given(method("GET"))
-            .and(path_regex(r"^/custom-path/\w+$")) // Accepts any /users/{id}
+            .and(path_regex(HttpProvider::get_user_by_id_path_regex())) // Accepts any /custom-path/{id}
             .respond_with(ResponseTemplate::new(200).set_body_json(response))
             .mount(&mock_server)
             .await;
 
         let url = Url::from_str(&mock_server.uri())?;
 
-        let provider = HttpProvider::new(url, Some(5000));
+        let provider = HttpProvider::new(url, Duration::from_secs(5));
 
         // Call the generated GET method with path params
         let path_params = MyPathParams {
@@ -183,6 +224,19 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_path_template_items() {
+        assert_eq!(
+            HttpProvider::GET_USER_BY_ID_PATH_TEMPLATE,
+            "/custom-path/{id}"
+        );
+        assert_eq!(
+            HttpProvider::get_user_by_id_path_regex(),
+            r"^/custom\-path/[^/]+$"
+        );
+        assert_eq!(HttpProvider::FETCH_A_PATH_TEMPLATE, "/custom-path");
+    }
+
     #[tokio::test]
     async fn test_successful_post_response() -> Result<(), Box<dyn std::error::Error>> {
         // Start the mock server
@@ -207,7 +261,7 @@ mod tests {
         let url = Url::from_str(&mock_server.uri())?;
 
         // Instantiate the provider (using the macro-generated OrderbookProvider)
-        let provider = HttpProvider::new(url, Some(5000));
+        let provider = HttpProvider::new(url, Duration::from_secs(5));
 
         // Prepare the request body
         let req = MyRequest {
@@ -249,7 +303,7 @@ mod tests {
             .await;
 
         let url = reqwest::Url::from_str(&mock_server.uri())?;
-        let provider = HttpProvider::new(url, Some(5000));
+        let provider = HttpProvider::new(url, Duration::from_secs(5));
 
         let req = MyRequest {
             query: "test put".to_string(),
@@ -284,7 +338,7 @@ mod tests {
             .await;
 
         let url = reqwest::Url::from_str(&mock_server.uri())?;
-        let provider = HttpProvider::new(url, Some(5000));
+        let provider = HttpProvider::new(url, Duration::from_secs(5));
 
         let result = provider.delete_d().await?;
 
@@ -297,4 +351,249 @@ mod tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_successful_patch_response() -> Result<(), Box<dyn std::error::Error>> {
+        use wiremock::matchers::{body_json, path};
+
+        let mock_server = MockServer::start().await;
+
+        let response = Response::<MyResponse> {
+            status: Status::Ok,
+            result: Some(MyResponse {
+                value: "Patch success".to_string(),
+            }),
+            error: None,
+        };
+
+        Mock::given(method("PATCH"))
+            .and(path("/users/42"))
+            .and(body_json(serde_json::json!({ "query": "rename" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            HttpProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        // The name is generated from the method and path
+        let result = provider
+            .patch_users_id(
+                &MyPathParams {
+                    id: "42".to_string(),
+                },
+                &MyRequest {
+                    query: "rename".to_string(),
+                },
+            )
+            .await?;
+
+        assert_eq!(result.status, Status::Ok);
+        assert_eq!(
+            result.result,
+            Some(MyResponse {
+                value: "Patch success".to_string()
+            })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_head_returns_headers() -> Result<(), Box<dyn std::error::Error>> {
+        use wiremock::matchers::path;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/objects/1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v1\"")
+                    .insert_header("x-object-size", "2048"),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            HttpProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let headers = provider
+            .head_objects_id(&MyPathParams {
+                id: "1".to_string(),
+            })
+            .await?;
+        assert_eq!(headers["etag"], "\"v1\"");
+        assert_eq!(headers["x-object-size"], "2048");
+
+        let error = provider
+            .head_objects_id(&MyPathParams {
+                id: "2".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error, "HTTP request failed with status 404: Not Found");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_options_returns_headers_or_body() -> Result<(), Box<dyn std::error::Error>> {
+        use wiremock::matchers::path;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("OPTIONS"))
+            .and(path("/users"))
+            .respond_with(
+                ResponseTemplate::new(204)
+                    .insert_header("allow", "GET, POST, OPTIONS")
+                    .insert_header("access-control-allow-origin", "*"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("OPTIONS"))
+            .and(path("/capabilities"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(["search", "export"]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            HttpProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let headers = provider.options_users().await?;
+        assert_eq!(headers["allow"], "GET, POST, OPTIONS");
+        assert_eq!(headers["access-control-allow-origin"], "*");
+
+        assert_eq!(provider.options_capabilities().await?, ["search", "export"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_custom_method_literal() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("REPORT"))
+            .and(wiremock::matchers::path("/calendars/work"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(["a", "b"]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            HttpProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        // The generated name is the lowercased verb followed by the path
+        assert_eq!(provider.report_calendars_work().await?, ["a", "b"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_with_query_and_headers() -> Result<(), Box<dyn std::error::Error>> {
+        use wiremock::matchers::{header, path, query_param};
+
+        let mock_server = MockServer::start().await;
+
+        let response = Response::<MyResponse> {
+            status: Status::Ok,
+            result: Some(MyResponse {
+                value: "Sessions deleted".to_string(),
+            }),
+            error: None,
+        };
+
+        // Only answer when both the query string and the header made it onto the DELETE
+        Mock::given(method("DELETE"))
+            .and(path("/sessions"))
+            .and(query_param("user_id", "42"))
+            .and(header("x-custom-header", "myvalue"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = HttpProvider::new(url, Duration::from_secs(5));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-custom-header", "myvalue".parse()?);
+
+        let result = provider
+            .delete_sessions(
+                &SessionQuery {
+                    user_id: "42".to_string(),
+                },
+                headers,
+            )
+            .await?;
+
+        assert_eq!(result.status, Status::Ok);
+        assert_eq!(
+            result.result,
+            Some(MyResponse {
+                value: "Sessions deleted".to_string()
+            })
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error_status_includes_body() -> Result<(), Box<dyn std::error::Error>> {
+        use wiremock::matchers::path;
+
+        let mock_server = MockServer::start().await;
+        let provider =
+            HttpProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        let user = MyPathParams {
+            id: "1".to_string(),
+        };
+
+        // The server's explanation is appended to the message
+        let mock = Mock::given(method("GET"))
+            .and(path("/custom-path/1"))
+            .respond_with(
+                ResponseTemplate::new(422).set_body_string("{\"error\": \"name is required\"}\n"),
+            )
+            .mount_as_scoped(&mock_server)
+            .await;
+        assert_eq!(
+            provider.get_user_by_id(&user).await.unwrap_err(),
+            "HTTP request failed with status 422: Unprocessable Entity: {\"error\": \"name is required\"}"
+        );
+        drop(mock);
+
+        // Huge bodies are cut at 2 KiB, on a character boundary
+        let mock = Mock::given(method("GET"))
+            .and(path("/custom-path/1"))
+            .respond_with(
+                ResponseTemplate::new(500).set_body_string("x".to_string() + &"é".repeat(4096)),
+            )
+            .mount_as_scoped(&mock_server)
+            .await;
+        let error = provider.get_user_by_id(&user).await.unwrap_err();
+        let body = error
+            .strip_prefix("HTTP request failed with status 500: Internal Server Error: ")
+            .unwrap();
+        assert_eq!(body, "x".to_string() + &"é".repeat(1023) + "...");
+        drop(mock);
+
+        // Invalid UTF-8 is replaced rather than failing the error path
+        let _mock = Mock::given(method("GET"))
+            .and(path("/custom-path/1"))
+            .respond_with(ResponseTemplate::new(502).set_body_bytes(b"bad \xff gateway".to_vec()))
+            .mount_as_scoped(&mock_server)
+            .await;
+        assert_eq!(
+            provider.get_user_by_id(&user).await.unwrap_err(),
+            "HTTP request failed with status 502: Bad Gateway: bad \u{fffd} gateway"
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+    /// Query strings are flat, so the nested map can't be serialized.
+    #[derive(Serialize)]
+    struct NestedQuery {
+        page: u32,
+        filters: BTreeMap<u32, BTreeMap<u32, String>>,
+    }
+
+    http_provider!(
+        SearchProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/search",
+                method: GET,
+                fn_name: search,
+                res: Vec<String>,
+                query_params: NestedQuery,
+                static_query: { mode: "full" },
+            },
+        }
+    );
+
+    #[tokio::test]
+    async fn test_unserializable_query_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(["hit"]))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            SearchProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        let query = NestedQuery {
+            page: 1,
+            filters: BTreeMap::from([(1, BTreeMap::from([(2, "x".to_string())]))]),
+        };
+
+        let error = provider.search(&query).await.unwrap_err();
+        assert!(
+            error.starts_with("Failed to serialize query parameters of `search`: "),
+            "{}",
+            error
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty_nested_map_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let provider =
+            SearchProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        let query = NestedQuery {
+            page: 2,
+            filters: BTreeMap::new(),
+        };
+
+        // Not silently sent as `mode=full&page=2`
+        let error = provider.search(&query).await.unwrap_err();
+        assert!(error.contains("unsupported value"), "{}", error);
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Chunk {
+        data: String,
+    }
+
+    http_provider!(
+        ArtifactProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/artifacts/build.log",
+                method: GET,
+                fn_name: get_log,
+                res: Chunk,
+                range: true,
+            },
+        }
+    );
+
+    /// Matches requests without a `Range` header.
+    struct NoRange;
+
+    impl wiremock::Match for NoRange {
+        fn matches(&self, request: &Request) -> bool {
+            !request
+                .headers
+                .iter()
+                .any(|(name, _)| name.as_str() == "range")
+        }
+    }
+
+    fn partial(data: &str, content_range: &str) -> ResponseTemplate {
+        ResponseTemplate::new(206)
+            .insert_header("content-range", content_range)
+            .set_body_json(Chunk {
+                data: data.to_string(),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_range_header_formatting() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/artifacts/build.log"))
+            .and(header("range", "bytes=0-99"))
+            .respond_with(partial("head", "bytes 0-99/300"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/artifacts/build.log"))
+            .and(header("range", "bytes=100-"))
+            .respond_with(partial("tail", "bytes 100-299/300"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/artifacts/build.log"))
+            .and(NoRange)
+            .respond_with(ResponseTemplate::new(200).set_body_json(Chunk {
+                data: "all".to_string(),
+            }))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            ArtifactProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(provider.get_log(Some((0, Some(99)))).await?.data, "head");
+        assert_eq!(provider.get_log(Some((100, None))).await?.data, "tail");
+        assert_eq!(provider.get_log(None).await?.data, "all");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsatisfiable_range_is_error() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/artifacts/build.log"))
+            .respond_with(ResponseTemplate::new(416))
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            ArtifactProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(
+            provider.get_log(Some((500, None))).await,
+            Err("HTTP request failed with status 416: Range Not Satisfiable".to_string())
+        );
+
+        Ok(())
+    }
+}
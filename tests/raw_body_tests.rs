@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Deserialize;
+    use sha2::{Digest, Sha256};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct ArtifactMetadata {
+        name: String,
+        size: u64,
+    }
+
+    http_provider!(
+        ArtifactProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/artifacts/latest",
+                method: GET,
+                fn_name: latest_metadata,
+                res: ArtifactMetadata,
+                keep_raw_body: true,
+            },
+            {
+                path: "/artifacts/wrapped",
+                method: GET,
+                fn_name: wrapped_metadata,
+                res: ArtifactMetadata,
+                keep_raw_body: true,
+                envelope: { status_field: status, ok_value: "Ok", error_field: error, data_field: result },
+            },
+        }
+    );
+
+    // Key order and whitespace that re-serializing the parsed value wouldn't reproduce
+    const BODY: &str = "{ \"size\": 1024,\n  \"name\": \"agent-v2.tar.gz\" }";
+
+    #[tokio::test]
+    async fn test_raw_body_matches_wire_bytes() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(BODY))
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            ArtifactProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        let (metadata, raw_body) = provider.latest_metadata().await?;
+
+        assert_eq!(
+            metadata,
+            ArtifactMetadata {
+                name: "agent-v2.tar.gz".to_string(),
+                size: 1024,
+            }
+        );
+        assert_eq!(Sha256::digest(&raw_body), Sha256::digest(BODY.as_bytes()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_raw_body_includes_envelope() -> Result<(), Box<dyn std::error::Error>> {
+        let body = r#"{"status":"Ok","result":{"name":"a","size":1},"error":null}"#;
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            ArtifactProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        let (metadata, raw_body) = provider.wrapped_metadata().await?;
+
+        assert_eq!(metadata.name, "a");
+        assert_eq!(raw_body, body.as_bytes());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Deserialize;
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Deserialize)]
+    struct Account {
+        #[allow(dead_code)]
+        id: u32,
+    }
+
+    http_provider!(
+        AccountProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/account",
+                method: GET,
+                fn_name: account,
+                res: Account,
+            },
+            {
+                path: "/account",
+                method: GET,
+                fn_name: account_off_thread,
+                res: Account,
+                deserialize_off_thread: true,
+            },
+            {
+                path: "/large",
+                method: GET,
+                fn_name: large,
+                res: Account,
+            },
+        }
+    );
+
+    http_provider!(
+        AccountV2Provider,
+        behavior_version: 2,
+        {
+            {
+                path: "/account",
+                method: GET,
+                fn_name: account,
+                res: Account,
+            },
+        }
+    );
+
+    /// A body with the wrong shape, holding secrets at several depths.
+    const BODY: &str = r#"{"id": "7", "password": "hunter2", "session": {"token": "abc", "scopes": ["a", "b"]}, "api_key": 12345}"#;
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(BODY))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/large"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(format!("{{\"id\": \"{}\"}}", "é".repeat(40 * 1024))),
+            )
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    fn builder(mock_server: &MockServer) -> AccountProviderBuilder {
+        AccountProvider::builder(Url::from_str(&mock_server.uri()).unwrap())
+            .timeout(Duration::from_secs(5))
+    }
+
+    #[tokio::test]
+    async fn test_body_is_not_captured_by_default() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider = builder(&mock_server).build()?;
+
+        let error = provider.account().await.unwrap_err();
+        assert!(error.starts_with("Failed to deserialize response: "));
+        assert!(!error.contains("hunter2"), "{}", error);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_captured_body_is_redacted() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let hook_reported = reported.clone();
+        let provider = builder(&mock_server)
+            .capture_raw_on_decode_error(true)
+            .redact_captured_fields(["password", "session"])
+            .redact_captured_fields(["api_key".to_string()])
+            .build()?
+            .with_error_hook(move |_, error| hook_reported.lock().unwrap().push(error.to_string()));
+
+        let expected = r#"response body: {"id": "7", "password": "[REDACTED]", "session": "[REDACTED]", "api_key": "[REDACTED]"}"#;
+        for error in [
+            provider.account().await.unwrap_err(),
+            provider.account_off_thread().await.unwrap_err(),
+        ] {
+            assert!(error.starts_with("Failed to deserialize response: "));
+            assert!(error.ends_with(expected), "{}", error);
+        }
+
+        // The error hook sees the same message
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.len(), 2);
+        assert!(reported.iter().all(|error| error.ends_with(expected)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_captured_body_is_capped() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider = builder(&mock_server)
+            .capture_raw_on_decode_error(true)
+            .build()?;
+
+        // 80 KiB of two-byte characters, cut at a character boundary
+        let error = provider.large().await.unwrap_err();
+        let captured = error.split_once("; response body: ").unwrap().1;
+        assert!(
+            captured.ends_with("... (81930 bytes in total)"),
+            "{}",
+            &error[error.len() - 40..]
+        );
+        assert!(captured.len() < 64 * 1024 + 30);
+        assert!(captured.starts_with("{\"id\": \"éé"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_version_2_error_holds_capture() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri())?;
+
+        let provider = AccountV2Provider::builder(url.clone())
+            .capture_raw_on_decode_error(true)
+            .redact_captured_fields(["password"])
+            .build()?;
+        let error = provider.account().await.unwrap_err();
+        assert!(error.to_string().contains(r#""password": "[REDACTED]""#));
+        match error {
+            AccountV2ProviderError::SchemaMismatch { body, captured, .. } => {
+                assert_eq!(body, BODY);
+                assert!(captured.unwrap().contains(r#""token": "abc""#));
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        let provider = AccountV2Provider::new(url, Duration::from_secs(5));
+        match provider.account().await.unwrap_err() {
+            AccountV2ProviderError::SchemaMismatch { captured, .. } => assert!(captured.is_none()),
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+//! `res_format: raw` endpoints build the request as usual but return the `reqwest::Response`
+//! without reading it, whatever its status.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, StatusCode, Url};
+    use serde::Serialize;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct ExportPath {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct ExportQuery {
+        format: String,
+    }
+
+    http_provider!(
+        ExportProvider,
+        behavior_version: 2,
+        {
+            { path: "/exports/{id}", method: GET, fn_name: export, path_params: ExportPath, query_params: ExportQuery, headers: HeaderMap, res_format: raw },
+            { path: "/exports/{id}", method: HEAD, fn_name: probe, path_params: ExportPath, res_format: raw },
+            { path: "/flaky", method: GET, fn_name: flaky, res_format: raw, retries: 1 },
+        }
+    );
+
+    const CSV: &str = "id,name\n1,widget\n";
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/exports/1"))
+            .and(query_param("format", "csv"))
+            .and(header("x-request-id", "42"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-row-count", "1")
+                    .set_body_raw(CSV, "text/csv"),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/exports/2"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("export failed"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/exports/1"))
+            .respond_with(ResponseTemplate::new(202).insert_header("retry-after", "5"))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    fn query() -> ExportQuery {
+        ExportQuery {
+            format: "csv".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_response_is_returned_unread() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            ExportProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", "42".parse()?);
+        let mut response = provider
+            .export(&ExportPath { id: 1 }, &query(), headers)
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-row-count"], "1");
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            body.extend_from_slice(&chunk);
+        }
+        assert_eq!(body, CSV.as_bytes());
+
+        // A HEAD endpoint returns the response too, not only its headers
+        let response = provider.probe(&ExportPath { id: 1 }).await?;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        assert_eq!(response.headers()["retry-after"], "5");
+
+        // Version 2 doesn't ask for JSON on endpoints that don't decode it
+        let requests = mock_server.received_requests().await.unwrap();
+        let accept = requests[0]
+            .headers
+            .iter()
+            .find(|(name, _)| name.as_str().eq_ignore_ascii_case("accept"))
+            .map(|(_, values)| values.last().as_str().to_string());
+        assert_eq!(accept.as_deref(), Some("*/*"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error_statuses_are_not_errors() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ExportProvider::new(url, Duration::from_secs(5));
+
+        let response = provider
+            .export(&ExportPath { id: 2 }, &query(), HeaderMap::new())
+            .await?;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.text().await?, "export failed");
+
+        // Nothing is mounted there, so wiremock answers 404
+        let response = provider
+            .export(&ExportPath { id: 3 }, &query(), HeaderMap::new())
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // Retries still apply to 5xx responses
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&mock_server)
+            .await;
+        let response = provider.flaky().await?;
+        assert_eq!(response.text().await?, "ok");
+
+        // Transport failures are still errors
+        let provider =
+            ExportProvider::new(Url::from_str("http://127.0.0.1:1")?, Duration::from_secs(5));
+        assert!(provider.flaky().await.is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+//! `capture_redirect` endpoints return a 3xx response's status and `Location` instead of
+//! following the redirect.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, Url};
+    use serde::Serialize;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_json, header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct ExportRequest {
+        table: String,
+    }
+
+    #[derive(Serialize)]
+    struct ExportQuery {
+        format: String,
+    }
+
+    #[derive(Serialize)]
+    struct ProjectPath {
+        project: String,
+    }
+
+    http_provider!(
+        ExportProvider,
+        behavior_version: 1,
+        {
+            { path: "/projects/{project}/exports", method: POST, fn_name: create_export, path_params: ProjectPath, req: ExportRequest, query_params: ExportQuery, headers: HeaderMap, capture_redirect: true },
+            { path: "/latest", method: GET, fn_name: latest, capture_redirect: true },
+        }
+    );
+
+    http_provider!(
+        ExportV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/latest", method: GET, fn_name: latest, capture_redirect: true, not_found_as_none: true },
+        }
+    );
+
+    fn export() -> ExportRequest {
+        ExportRequest {
+            table: "orders".to_string(),
+        }
+    }
+
+    fn query() -> ExportQuery {
+        ExportQuery {
+            format: "csv".to_string(),
+        }
+    }
+
+    fn project() -> ProjectPath {
+        ProjectPath {
+            project: "acme".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redirect_is_returned() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/projects/acme/exports"))
+            .and(query_param("format", "csv"))
+            .and(header("x-request-id", "42"))
+            .and(body_json(serde_json::json!({ "table": "orders" })))
+            .respond_with(ResponseTemplate::new(303).insert_header("location", "/exports/7"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/latest"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("location", "https://cdn.example.com/exports/7.csv"),
+            )
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ExportProvider::new(url.clone(), Duration::from_secs(5));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", "42".parse()?);
+        let redirect = provider
+            .create_export(&project(), &export(), &query(), headers)
+            .await?;
+        assert_eq!(
+            redirect,
+            ExportProviderRedirect {
+                status: 303,
+                location: url.join("/exports/7")?,
+            }
+        );
+
+        let redirect = provider.latest().await?;
+        assert_eq!(redirect.status, 302);
+        assert_eq!(
+            redirect.location.as_str(),
+            "https://cdn.example.com/exports/7.csv"
+        );
+
+        // Neither redirect was followed
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unexpected_responses_fail() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let responses = [
+            (
+                "/projects/missing-location/exports",
+                ResponseTemplate::new(303),
+            ),
+            (
+                "/projects/bad-location/exports",
+                ResponseTemplate::new(303).insert_header("location", "http://[::1"),
+            ),
+            (
+                "/projects/done/exports",
+                ResponseTemplate::new(200).set_body_string("{}"),
+            ),
+            (
+                "/projects/broken/exports",
+                ResponseTemplate::new(500).set_body_string("export failed"),
+            ),
+        ];
+        for (route, response) in responses {
+            Mock::given(method("POST"))
+                .and(path(route))
+                .respond_with(response)
+                .mount(&mock_server)
+                .await;
+        }
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ExportProvider::new(url.clone(), Duration::from_secs(5));
+        let create = |project: &str| {
+            let path = ProjectPath {
+                project: project.to_string(),
+            };
+            let provider = &provider;
+            async move {
+                provider
+                    .create_export(&path, &export(), &query(), HeaderMap::new())
+                    .await
+            }
+        };
+
+        let missing = "Redirect with status 303 has no valid `Location` header".to_string();
+        assert_eq!(create("missing-location").await, Err(missing.clone()));
+        assert_eq!(create("bad-location").await, Err(missing));
+        assert_eq!(
+            create("done").await,
+            Err("Expected a redirect, got status 200".to_string())
+        );
+        assert_eq!(
+            create("broken").await,
+            Err(
+                "HTTP request failed with status 500: Internal Server Error: export failed"
+                    .to_string()
+            )
+        );
+
+        // Version 2 reports the same failures, and `not_found_as_none` still applies
+        let provider = ExportV2Provider::new(url, Duration::from_secs(5));
+        assert_eq!(provider.latest().await?, None);
+        Mock::given(method("GET"))
+            .and(path("/latest"))
+            .respond_with(ResponseTemplate::new(307))
+            .mount(&mock_server)
+            .await;
+        assert_eq!(
+            provider.latest().await.unwrap_err().to_string(),
+            "Redirect with status 307 has no valid `Location` header"
+        );
+
+        Ok(())
+    }
+}
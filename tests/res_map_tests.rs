@@ -0,0 +1,128 @@
+//! `res_map` endpoints read the body into the type declared for the response status, returned
+//! as a variant of a generated enum.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Job {
+        id: u32,
+        output: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct QueuedJob {
+        id: u32,
+        position: u32,
+    }
+
+    #[derive(Serialize)]
+    struct JobPath {
+        id: u32,
+    }
+
+    http_provider!(
+        JobProvider,
+        behavior_version: 2,
+        {
+            { path: "/jobs/{id}", method: GET, fn_name: get_job, path_params: JobPath, res_map: { 200 => Job, 202 => QueuedJob } },
+            { path: "/jobs/{id}", method: GET, fn_name: find_job, path_params: JobPath, res_map: { 200 => Job, 202 => QueuedJob }, not_found_as_none: true, with_meta: true },
+            admin: {
+                prefix: "/admin",
+                { path: "/jobs/{id}", method: GET, fn_name: get_job, path_params: JobPath, res_map: { 200 => Job, 409 => QueuedJob } },
+            },
+        }
+    );
+
+    fn job() -> Job {
+        Job {
+            id: 1,
+            output: "done".to_string(),
+        }
+    }
+
+    fn queued() -> QueuedJob {
+        QueuedJob { id: 2, position: 4 }
+    }
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        let responses = [
+            ("/jobs/1", ResponseTemplate::new(200).set_body_json(job())),
+            (
+                "/jobs/2",
+                ResponseTemplate::new(202).set_body_json(queued()),
+            ),
+            ("/jobs/3", ResponseTemplate::new(201).set_body_json(job())),
+            ("/jobs/4", ResponseTemplate::new(202).set_body_json(job())),
+            (
+                "/admin/jobs/2",
+                ResponseTemplate::new(409).set_body_json(queued()),
+            ),
+        ];
+        for (route, response) in responses {
+            Mock::given(method("GET"))
+                .and(path(route))
+                .respond_with(response)
+                .mount(&mock_server)
+                .await;
+        }
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_status_selects_the_variant() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider = JobProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        match provider.get_job(&JobPath { id: 1 }).await? {
+            JobProviderGetJobResponse::Ok(found) => assert_eq!(found, job()),
+            JobProviderGetJobResponse::Accepted(found) => panic!("unexpected job: {:?}", found),
+        }
+        match provider.get_job(&JobPath { id: 2 }).await? {
+            JobProviderGetJobResponse::Accepted(found) => assert_eq!(found, queued()),
+            JobProviderGetJobResponse::Ok(found) => panic!("unexpected job: {:?}", found),
+        }
+
+        // Composes with the other result wrappers, and names group enums after the group
+        let response = provider.find_job(&JobPath { id: 2 }).await?.unwrap();
+        assert_eq!(response.status, 202);
+        match response.body {
+            JobProviderFindJobResponse::Accepted(found) => assert_eq!(found, queued()),
+            JobProviderFindJobResponse::Ok(found) => panic!("unexpected job: {:?}", found),
+        }
+        assert!(provider.find_job(&JobPath { id: 5 }).await?.is_none());
+        match provider.admin().get_job(&JobPath { id: 2 }).await? {
+            JobProviderAdminGetJobResponse::Conflict(found) => assert_eq!(found, queued()),
+            JobProviderAdminGetJobResponse::Ok(found) => panic!("unexpected job: {:?}", found),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_statuses_fail() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider = JobProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        match provider.get_job(&JobPath { id: 3 }).await {
+            Err(JobProviderError::Status { code, .. }) => assert_eq!(code, 201),
+            result => panic!("unexpected result: {:?}", result.err()),
+        }
+        // A body that doesn't match the status' type fails to deserialize
+        assert!(matches!(
+            provider.get_job(&JobPath { id: 4 }).await,
+            Err(JobProviderError::SchemaMismatch { .. })
+        ));
+
+        Ok(())
+    }
+}
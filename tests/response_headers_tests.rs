@@ -0,0 +1,195 @@
+//! `res_headers` endpoints read response headers into the fields of a struct, returned
+//! alongside the deserialized body.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderValue, Url};
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct PageQuery {
+        page: u32,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct PageInfo {
+        total_count: u64,
+        next_page: Option<u32>,
+        etag: Option<HeaderValue>,
+    }
+
+    /// A header field type the provider doesn't know about.
+    #[derive(Debug, PartialEq)]
+    struct Cursor(String);
+
+    impl UserProviderFromHeader for Cursor {
+        fn from_header(value: &HeaderValue) -> Result<Self, String> {
+            value
+                .to_str()
+                .map(|cursor| Cursor(cursor.to_string()))
+                .map_err(|e| e.to_string())
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct CursorInfo {
+        cursor: Cursor,
+    }
+
+    http_provider!(
+        UserProvider,
+        behavior_version: 1,
+        {
+            { path: "/users", method: GET, fn_name: list, query_params: PageQuery, res: Vec<User>, res_headers: PageInfo { total_count: "X-Total-Count", next_page: "X-Next-Page", etag } },
+            { path: "/users", method: GET, fn_name: list_raw, query_params: PageQuery, res: Vec<User>, keep_raw_body: true, res_headers: PageInfo { total_count: "x-total-count", next_page: "x-next-page", etag } },
+            { path: "/feed", method: GET, fn_name: feed, res: Vec<User>, res_headers: CursorInfo { cursor: "x-cursor" } },
+        }
+    );
+
+    http_provider!(
+        UserV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/users", method: GET, fn_name: list, query_params: PageQuery, res: Vec<User>, res_headers: PageInfo { total_count: "x-total-count", next_page: "x-next-page", etag }, not_found_as_none: true },
+        }
+    );
+
+    fn users() -> Vec<User> {
+        vec![User { id: 1 }, User { id: 2 }]
+    }
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(query_param("page", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-total-count", " 42 ")
+                    .insert_header("x-next-page", "2")
+                    .insert_header("etag", "\"v7\"")
+                    .set_body_json(users()),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-total-count", "42")
+                    .set_body_json(users()),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(query_param("page", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(users()))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(query_param("page", "4"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-total-count", "many")
+                    .set_body_json(users()),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/feed"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-cursor", "abc")
+                    .set_body_json(users()),
+            )
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_headers_are_read_into_the_struct() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url.clone(), Duration::from_secs(5));
+
+        let (page, list) = provider.list(&PageQuery { page: 1 }).await?;
+        assert_eq!(
+            page,
+            PageInfo {
+                total_count: 42,
+                next_page: Some(2),
+                etag: Some(HeaderValue::from_static("\"v7\"")),
+            }
+        );
+        assert_eq!(list, users());
+
+        // Missing headers of `Option` fields are `None`
+        let (page, _) = provider.list(&PageQuery { page: 2 }).await?;
+        assert_eq!(
+            page,
+            PageInfo {
+                total_count: 42,
+                next_page: None,
+                etag: None,
+            }
+        );
+
+        let (page, (list, raw_body)) = provider.list_raw(&PageQuery { page: 2 }).await?;
+        assert_eq!(page.total_count, 42);
+        assert_eq!(list, users());
+        assert_eq!(raw_body.as_ref(), serde_json::to_vec(&users())?.as_slice());
+
+        let (info, _) = provider.feed().await?;
+        assert_eq!(info.cursor, Cursor("abc".to_string()));
+
+        let provider = UserV2Provider::new(url, Duration::from_secs(5));
+        let (page, _) = provider.list(&PageQuery { page: 1 }).await?.unwrap();
+        assert_eq!(page.next_page, Some(2));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unusable_headers_fail() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url.clone(), Duration::from_secs(5));
+
+        assert_eq!(
+            provider.list(&PageQuery { page: 3 }).await.unwrap_err(),
+            "Missing response header `x-total-count`"
+        );
+        let error = provider.list(&PageQuery { page: 4 }).await.unwrap_err();
+        assert_eq!(
+            error,
+            "Invalid response header `x-total-count`: invalid digit found in string"
+        );
+
+        // Error statuses are reported before any header is read
+        let provider = UserV2Provider::new(url, Duration::from_secs(5));
+        assert!(provider.list(&PageQuery { page: 5 }).await?.is_none());
+        match provider.list(&PageQuery { page: 3 }).await.unwrap_err() {
+            UserV2ProviderError::Other(message) => {
+                assert_eq!(message, "Missing response header `x-total-count`")
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+
+        Ok(())
+    }
+}
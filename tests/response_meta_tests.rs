@@ -0,0 +1,130 @@
+//! `with_meta` endpoints return the response status and headers along with the result, while
+//! endpoints without it keep returning the result alone.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u32,
+        name: String,
+    }
+
+    #[derive(Serialize)]
+    struct UserPath {
+        id: u32,
+    }
+
+    http_provider!(
+        UserProvider,
+        behavior_version: 1,
+        {
+            { path: "/users/{id}", method: GET, fn_name: get, path_params: UserPath, res: User },
+            { path: "/users/{id}", method: GET, fn_name: get_with_meta, path_params: UserPath, res: User, with_meta: true },
+            { path: "/users/{id}", method: GET, fn_name: find_with_meta, path_params: UserPath, res: User, with_meta: true, not_found_as_none: true },
+            { path: "/users/{id}", method: GET, fn_name: get_raw_with_meta, path_params: UserPath, res: User, with_meta: true, keep_raw_body: true },
+            { path: "/users", method: POST, fn_name: create, req: User, res: (), with_meta: true },
+        }
+    );
+
+    http_provider!(
+        UserV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/users/{id}", method: GET, fn_name: get, path_params: UserPath, res: User, with_meta: true },
+        }
+    );
+
+    fn user() -> User {
+        User {
+            id: 1,
+            name: "Ada".to_string(),
+        }
+    }
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-ratelimit-remaining", "41")
+                    .insert_header("x-ratelimit-reset", "1700000000")
+                    .set_body_json(user()),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/2"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("no such user"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .respond_with(ResponseTemplate::new(201).insert_header("location", "/users/1"))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_status_and_headers_are_returned() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url.clone(), Duration::from_secs(5));
+        let item = UserPath { id: 1 };
+
+        let response = provider.get_with_meta(&item).await?;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.headers["x-ratelimit-remaining"], "41");
+        assert_eq!(response.headers["x-ratelimit-reset"], "1700000000");
+        assert_eq!(response.body, user());
+
+        // Endpoints without `with_meta` keep their signatures
+        let plain: User = provider.get(&item).await?;
+        assert_eq!(plain, response.body);
+
+        let response = provider.create(&user()).await?;
+        assert_eq!(response.status, 201);
+        assert_eq!(response.headers["location"], "/users/1");
+
+        let response = provider.get_raw_with_meta(&item).await?;
+        let (body, raw_body) = response.body;
+        assert_eq!(body, user());
+        assert_eq!(raw_body.as_ref(), serde_json::to_vec(&user())?.as_slice());
+        assert_eq!(response.headers["x-ratelimit-remaining"], "41");
+
+        let response = UserV2Provider::new(url, Duration::from_secs(5))
+            .get(&item)
+            .await?;
+        assert_eq!((response.status, response.body), (200, user()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error_statuses_still_fail() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            UserProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        let missing = UserPath { id: 2 };
+
+        assert_eq!(
+            provider.get_with_meta(&missing).await.unwrap_err(),
+            "HTTP request failed with status 404: Not Found: no such user"
+        );
+        assert!(provider.find_with_meta(&missing).await?.is_none());
+        let found = provider.find_with_meta(&UserPath { id: 1 }).await?;
+        assert_eq!(found.map(|response| response.body), Some(user()));
+
+        Ok(())
+    }
+}
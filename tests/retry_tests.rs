@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize, Serializer};
+    use std::time::Duration;
+    use std::{
+        str::FromStr,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    http_provider!(
+        RetryProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/jobs",
+                method: POST,
+                fn_name: create_job,
+                req: TrackedBody,
+                res: Job,
+                retries: 2,
+            },
+            {
+                path: "/jobs",
+                method: GET,
+                fn_name: list_jobs,
+                res: Vec<Job>,
+            },
+        }
+    );
+
+    /// Request body counting how many times it gets serialized.
+    struct TrackedBody {
+        name: String,
+        serializations: Arc<AtomicUsize>,
+    }
+
+    impl TrackedBody {
+        fn new(name: &str) -> Self {
+            Self {
+                name: name.to_string(),
+                serializations: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+    }
+
+    impl Serialize for TrackedBody {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.serializations.fetch_add(1, Ordering::SeqCst);
+            Job {
+                name: self.name.clone(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Job {
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_retry_reuses_serialized_body() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        let job = Job {
+            name: "build".to_string(),
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/jobs"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/jobs"))
+            .and(body_json(&job))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&job))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = RetryProvider::new(url, Duration::from_secs(5));
+
+        let body = TrackedBody::new("build");
+        let result = provider.create_job(&body).await?;
+
+        assert_eq!(result, job);
+        assert_eq!(body.serializations.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retries_exhausted_returns_last_status() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/jobs"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = RetryProvider::new(url, Duration::from_secs(5));
+
+        let err = provider
+            .create_job(&TrackedBody::new("build"))
+            .await
+            .unwrap_err();
+
+        assert!(err.contains("500"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_without_option() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/jobs"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = RetryProvider::new(url, Duration::from_secs(5));
+
+        assert!(provider.list_jobs().await.is_err());
+
+        Ok(())
+    }
+}
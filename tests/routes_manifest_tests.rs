@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    http_provider!(
+        CatalogProvider,
+        behavior_version: 1,
+        routes_manifest: true,
+        environments: {
+            dev: "http://localhost:8080/api",
+            prod: "https://deploy@api.example.com/",
+        },
+        {
+            {
+                path: "/items",
+                method: GET,
+                fn_name: list_items,
+                res: Vec<String>,
+            },
+            {
+                method: POST,
+                fn_name: ping,
+                res: String,
+            },
+            admin: {
+                prefix: "/admin",
+                {
+                    path: "/items",
+                    method: DELETE,
+                    fn_name: purge,
+                    res: String,
+                },
+                audit: {
+                    prefix: "/audit",
+                    {
+                        path: "/events",
+                        method: GET,
+                        fn_name: events,
+                        res: Vec<String>,
+                    },
+                },
+            },
+        }
+    );
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Manifest {
+        provider: String,
+        hosts: BTreeMap<String, String>,
+        routes: Vec<Route>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Route {
+        name: String,
+        group: Option<String>,
+        method: String,
+        path: Option<String>,
+    }
+
+    fn route(name: &str, group: Option<&str>, method: &str, path: Option<&str>) -> Route {
+        Route {
+            name: name.to_string(),
+            group: group.map(str::to_string),
+            method: method.to_string(),
+            path: path.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_manifest_matches_endpoint_metadata() {
+        let manifest: Manifest = serde_json::from_str(CatalogProvider::MANIFEST_JSON).unwrap();
+
+        assert_eq!(
+            manifest,
+            Manifest {
+                provider: "CatalogProvider".to_string(),
+                hosts: BTreeMap::from([
+                    ("dev".to_string(), "localhost:8080".to_string()),
+                    ("prod".to_string(), "api.example.com".to_string()),
+                ]),
+                routes: vec![
+                    route(
+                        "list_items",
+                        None,
+                        "GET",
+                        Some(CatalogProvider::LIST_ITEMS_PATH_TEMPLATE)
+                    ),
+                    route("ping", None, "POST", None),
+                    route(
+                        "purge",
+                        Some("admin"),
+                        "DELETE",
+                        Some(CatalogProviderAdmin::PURGE_PATH_TEMPLATE)
+                    ),
+                    route(
+                        "events",
+                        Some("admin.audit"),
+                        "GET",
+                        Some(CatalogProviderAdminAudit::EVENTS_PATH_TEMPLATE)
+                    ),
+                ],
+            }
+        );
+        assert_eq!(
+            CatalogProviderAdminAudit::EVENTS_PATH_TEMPLATE,
+            "/admin/audit/events"
+        );
+    }
+
+    #[test]
+    fn test_generated_test_writes_manifest() {
+        let dir = std::env::temp_dir().join(format!("routes-manifest-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // The generated test may see the variable too; it writes the same manifest
+        std::env::set_var("HTTP_PROVIDER_MANIFEST_DIR", &dir);
+
+        catalog_provider_routes_manifest();
+
+        let written = std::fs::read_to_string(dir.join("CatalogProvider.json")).unwrap();
+        assert_eq!(written, CatalogProvider::MANIFEST_JSON);
+    }
+}
@@ -0,0 +1,75 @@
+//! Providers using every option that doesn't need an async runtime, in a crate without a
+//! `tokio` dependency: generated code naming `tokio` wouldn't compile here.
+#![deny(warnings)]
+#![allow(dead_code)]
+
+use http_provider_macro::http_provider;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct UserPath {
+    pub id: u32,
+}
+
+#[derive(Serialize)]
+pub struct OrgPath {
+    pub org: String,
+}
+
+#[derive(Serialize)]
+pub struct UserQuery {
+    pub page: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiFailure {
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct DocumentMeta {
+    pub title: String,
+}
+
+#[derive(Clone)]
+pub struct PageInfo {
+    pub total: u64,
+}
+
+pub struct Outbox;
+
+impl PlainProviderResponseSink<User> for Outbox {
+    fn accept(&self, _: &User) {}
+}
+
+http_provider!(
+    PlainProvider,
+    behavior_version: 2,
+    api_version: "2024-01-01",
+    deadline_header: "x-deadline-ms",
+    routes_manifest: true,
+    method_override: true,
+    retry_transport_errors_once: true,
+    {
+        { path: "/users/{id}", method: GET, path_params: UserPath, query_params: UserQuery, headers: HeaderMap, res: User, retries: 2, not_found_as_none: true, with_meta: true },
+        { path: "/users", method: POST, req: User, res: User, err: ApiFailure, keep_raw_body: true, expect_status: 201, emit: Outbox },
+        { path: "/users/{id}", method: PUT, path_params: UserPath, req: User, res: User, optimistic_lock: true, args_struct: true },
+        { path: "/documents", method: POST, res: User, multipart: { meta: DocumentMeta (json), files: many } },
+        { path: "/export", method: GET, range: true, res_format: bytes, res_headers: PageInfo { total: "x-total-count" } },
+        { path: "/download", method: GET, follow_presigned: true },
+        { path: "/latest", method: GET, capture_redirect: true },
+        { path: "/raw", method: GET, res_format: raw },
+        orgs: {
+            prefix: "/orgs/{org}",
+            group_params: OrgPath,
+            { path: "/members", method: GET, res: Vec<User>, envelope: { status_field: status, ok_value: "ok", error_field: error, data_field: data } },
+        },
+    }
+);
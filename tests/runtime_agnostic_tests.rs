@@ -0,0 +1,89 @@
+//! The consumer crate in `tests/runtime_agnostic` uses every option that doesn't need an async
+//! runtime, without depending on `tokio`. Building it proves the generated code doesn't name
+//! `tokio`, and its resolved dependencies show nothing but reqwest's own transport brings it in.
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Runs a `cargo` command in the consumer crate, with its own target directory.
+fn cargo(args: &[&str]) -> Output {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/runtime_agnostic");
+    let output = Command::new(env!("CARGO"))
+        .args(args)
+        .current_dir(&crate_dir)
+        .env("CARGO_TARGET_DIR", crate_dir.join("target"))
+        .output()
+        .expect("cargo runs");
+    assert!(
+        output.status.success(),
+        "`cargo {}` failed in the runtime-agnostic crate:\n{}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+#[test]
+fn test_generated_code_builds_without_tokio() {
+    cargo(&["check"]);
+}
+
+#[test]
+fn test_only_reqwest_brings_in_tokio() {
+    let output = cargo(&["metadata", "--format-version", "1"]);
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let resolve = &metadata["resolve"];
+    let name_of = |id: &str| {
+        metadata["packages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|package| package["id"] == id)
+            .and_then(|package| package["name"].as_str())
+            .unwrap()
+            .to_string()
+    };
+    let dependencies_of = |id: &str| -> Vec<String> {
+        resolve["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|node| node["id"] == id)
+            .map(|node| {
+                node["dependencies"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|dependency| dependency.as_str().unwrap().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    // Walks the graph from the consumer without entering reqwest, so what's left is required by
+    // the consumer, the macro and the other dependencies generated code uses
+    let packages = metadata["packages"].as_array().unwrap();
+    assert!(
+        packages.iter().any(|package| package["name"] == "tokio"),
+        "reqwest is expected to bring in tokio"
+    );
+    let root = resolve["root"].as_str().unwrap().to_string();
+    let mut seen = HashSet::from([root.clone()]);
+    let mut queue = VecDeque::from([root]);
+    while let Some(id) = queue.pop_front() {
+        let name = name_of(&id);
+        assert_ne!(name, "tokio", "tokio is required outside reqwest");
+        if name == "reqwest" {
+            continue;
+        }
+        for dependency in dependencies_of(&id) {
+            if seen.insert(dependency.clone()) {
+                queue.push_back(dependency);
+            }
+        }
+    }
+    assert!(
+        seen.iter().any(|id| name_of(id) == "http-provider-macro"),
+        "the macro must be among the consumer's dependencies"
+    );
+}
@@ -0,0 +1,117 @@
+//! `serialize_with` endpoints send the bytes a custom function makes of the `req` body, e.g.
+//! a canonical encoding that is signed downstream.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_bytes, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct Transfer {
+        to: String,
+        amount: u64,
+        memo: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Receipt {
+        id: u32,
+    }
+
+    mod encoding {
+        use super::Transfer;
+
+        /// Fields in a fixed order without whitespace, and no `null` values.
+        pub fn canonical(transfer: &Transfer) -> Result<Vec<u8>, String> {
+            if transfer.amount == 0 {
+                return Err("amount must be positive".to_string());
+            }
+            let mut out = format!(r#"{{"amount":{},"to":"{}""#, transfer.amount, transfer.to);
+            if let Some(memo) = &transfer.memo {
+                out.push_str(&format!(r#","memo":"{}""#, memo));
+            }
+            out.push('}');
+            Ok(out.into_bytes())
+        }
+    }
+
+    http_provider!(
+        TransferProvider,
+        behavior_version: 1,
+        {
+            { path: "/transfers", method: POST, fn_name: create, req: Transfer, res: Receipt, serialize_with: encoding::canonical },
+            { path: "/transfers", method: POST, fn_name: create_args, req: Transfer, res: Receipt, serialize_with: encoding::canonical, args_struct: true },
+            { path: "/transfers/signed", method: POST, fn_name: create_signed, req: Transfer, res: Receipt, serialize_with: encoding::canonical, content_type: "application/vnd.bank+json" },
+        }
+    );
+
+    fn transfer(amount: u64) -> Transfer {
+        Transfer {
+            to: "acme".to_string(),
+            amount,
+            memo: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_bytes_are_sent() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/transfers"))
+            .and(header("content-type", "application/json"))
+            .and(body_bytes(r#"{"amount":5,"to":"acme"}"#.as_bytes()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Receipt { id: 1 }))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/transfers/signed"))
+            .and(header("content-type", "application/vnd.bank+json"))
+            .and(body_bytes(
+                r#"{"amount":5,"to":"acme","memo":"rent"}"#.as_bytes(),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Receipt { id: 2 }))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = TransferProvider::new(url, Duration::from_secs(5));
+
+        assert_eq!(provider.create(&transfer(5)).await?, Receipt { id: 1 });
+        let receipt = provider
+            .create_args(TransferProviderCreateArgsArgs { body: transfer(5) })
+            .await?;
+        assert_eq!(receipt, Receipt { id: 1 });
+        let memo = Transfer {
+            memo: Some("rent".to_string()),
+            ..transfer(5)
+        };
+        assert_eq!(provider.create_signed(&memo).await?, Receipt { id: 2 });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_serializer_errors_fail_before_sending() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Receipt { id: 1 }))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = TransferProvider::new(url, Duration::from_secs(5));
+
+        assert_eq!(
+            provider.create(&transfer(0)).await.unwrap_err(),
+            "Failed to serialize request body: amount must be positive"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+//! Pins the generated method signatures, so an accidental change of the parameter
+//! order fails this test suite instead of silently breaking callers.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::header::HeaderMap;
+    use serde::{Deserialize, Serialize};
+    use std::future::Future;
+
+    http_provider!(
+        PinnedProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/items/{id}",
+                method: PUT,
+                fn_name: all_params,
+                path_params: ItemPath,
+                req: ItemBody,
+                res: Item,
+                extension: ItemExtension,
+                range: true,
+                headers: reqwest::header::HeaderMap,
+                query_params: ItemQuery,
+            },
+            {
+                path: "/items",
+                method: POST,
+                fn_name: body_and_query,
+                query_params: ItemQuery,
+                req: ItemBody,
+                res: Item,
+            },
+            {
+                path: "/items/{id}",
+                method: GET,
+                fn_name: path_and_headers,
+                headers: reqwest::header::HeaderMap,
+                path_params: ItemPath,
+                res: Item,
+            },
+        }
+    );
+
+    #[derive(Serialize)]
+    struct ItemPath {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct ItemBody {
+        name: String,
+    }
+
+    #[derive(Serialize)]
+    struct ItemQuery {
+        q: String,
+    }
+
+    struct ItemExtension;
+
+    #[derive(Deserialize)]
+    struct Item {}
+
+    fn pin_all<'a, F, Fut>(_method: F)
+    where
+        F: Fn(
+            &'a PinnedProvider,
+            &'a ItemPath,
+            &'a ItemBody,
+            &'a ItemQuery,
+            HeaderMap,
+            Option<(u64, Option<u64>)>,
+            ItemExtension,
+        ) -> Fut,
+        Fut: Future<Output = Result<Item, String>>,
+    {
+    }
+
+    fn pin_body_and_query<'a, F, Fut>(_method: F)
+    where
+        F: Fn(&'a PinnedProvider, &'a ItemBody, &'a ItemQuery) -> Fut,
+        Fut: Future<Output = Result<Item, String>>,
+    {
+    }
+
+    fn pin_path_and_headers<'a, F, Fut>(_method: F)
+    where
+        F: Fn(&'a PinnedProvider, &'a ItemPath, HeaderMap) -> Fut,
+        Fut: Future<Output = Result<Item, String>>,
+    {
+    }
+
+    #[test]
+    fn test_parameter_order_is_pinned() {
+        // Canonical order: path_params, body, query_params, headers, range, extension
+        pin_all(PinnedProvider::all_params);
+        pin_body_and_query(PinnedProvider::body_and_query);
+        pin_path_and_headers(PinnedProvider::path_and_headers);
+    }
+}
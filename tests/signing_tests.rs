@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+        str::FromStr,
+    };
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
+
+    http_provider!(
+        SignedProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/payments",
+                method: POST,
+                fn_name: create_payment,
+                req: Payment,
+                res: Payment,
+                retries: 1,
+            },
+        }
+    );
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Payment {
+        amount: u64,
+        currency: String,
+    }
+
+    fn body_hash(body: &[u8]) -> String {
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Matches requests whose `x-body-hash` header is the hash of the received body.
+    struct BodyHashMatches;
+
+    impl wiremock::Match for BodyHashMatches {
+        fn matches(&self, request: &Request) -> bool {
+            request
+                .headers
+                .iter()
+                .find(|(name, _)| name.as_str() == "x-body-hash")
+                .map(|(_, values)| values.last().as_str() == body_hash(&request.body))
+                .unwrap_or(false)
+        }
+    }
+
+    fn signer(request: &mut reqwest::Request) -> Result<(), String> {
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .ok_or("request has no buffered body")?;
+        let hash = body_hash(body);
+        request
+            .headers_mut()
+            .insert("x-body-hash", hash.parse().map_err(|_| "invalid hash")?);
+        Ok(())
+    }
+
+    fn payment() -> Payment {
+        Payment {
+            amount: 1250,
+            currency: "EUR".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signed_body_matches_received_body() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/payments"))
+            .and(BodyHashMatches)
+            .respond_with(ResponseTemplate::new(200).set_body_json(payment()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = SignedProvider::new(url, Duration::from_secs(5)).with_request_signer(signer);
+
+        assert_eq!(provider.create_payment(&payment()).await?, payment());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retried_request_keeps_signature() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(BodyHashMatches)
+            .respond_with(ResponseTemplate::new(502))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(BodyHashMatches)
+            .respond_with(ResponseTemplate::new(200).set_body_json(payment()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = SignedProvider::new(url, Duration::from_secs(5)).with_request_signer(signer);
+
+        assert_eq!(provider.create_payment(&payment()).await?, payment());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signer_error_aborts_call() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(payment()))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = SignedProvider::new(url, Duration::from_secs(5))
+            .with_request_signer(|_| Err("signing key unavailable".to_string()));
+
+        let err = provider.create_payment(&payment()).await.unwrap_err();
+        assert_eq!(err, "signing key unavailable");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    http_provider!(
+        DashboardProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/summary",
+                method: GET,
+                fn_name: summary,
+                query_params: SummaryQuery,
+                res: Summary,
+                single_flight: true,
+            },
+        }
+    );
+
+    #[derive(Serialize)]
+    struct SummaryQuery {
+        range: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct Summary {
+        visits: u32,
+    }
+
+    fn query(range: &str) -> SummaryQuery {
+        SummaryQuery {
+            range: range.to_string(),
+        }
+    }
+
+    /// Starts a server answering `/summary` after `delay`, so concurrent calls overlap.
+    async fn slow_server(
+        delay: Duration,
+    ) -> Result<(MockServer, DashboardProvider), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/summary"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(Summary { visits: 42 })
+                    .set_delay(delay),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = DashboardProvider::new(url, Duration::from_secs(5));
+        Ok((mock_server, provider))
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_calls_share_one_request() -> Result<(), Box<dyn std::error::Error>> {
+        let (mock_server, provider) = slow_server(Duration::from_millis(300)).await?;
+
+        let calls: Vec<_> = (0..20)
+            .map(|_| {
+                let provider = provider.clone();
+                tokio::spawn(async move { provider.summary(&query("week")).await })
+            })
+            .collect();
+        for call in calls {
+            assert_eq!(call.await??, Summary { visits: 42 });
+        }
+
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_flight_ends_with_the_request() -> Result<(), Box<dyn std::error::Error>> {
+        let (mock_server, provider) = slow_server(Duration::ZERO).await?;
+
+        provider.summary(&query("week")).await?;
+        provider.summary(&query("week")).await?;
+
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_flights_are_keyed_by_url() -> Result<(), Box<dyn std::error::Error>> {
+        let (mock_server, provider) = slow_server(Duration::from_millis(300)).await?;
+
+        let (week, month) = (query("week"), query("month"));
+        let (week, month) = tokio::join!(provider.summary(&week), provider.summary(&month));
+        week?;
+        month?;
+
+        let received = mock_server.received_requests().await.unwrap();
+        let mut queries: Vec<_> = received.iter().map(|r| r.url.query().unwrap()).collect();
+        queries.sort();
+        assert_eq!(queries, ["range=month", "range=week"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_waiting_call_survives_cancelled_flight() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let (mock_server, provider) = slow_server(Duration::from_millis(300)).await?;
+
+        let leader = tokio::spawn({
+            let provider = provider.clone();
+            async move { provider.summary(&query("week")).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let follower = tokio::spawn({
+            let provider = provider.clone();
+            async move { provider.summary(&query("week")).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        leader.abort();
+
+        // The waiting call sends its own request instead of hanging or failing
+        assert_eq!(follower.await??, Summary { visits: 42 });
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Serialize;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct Filter {
+        #[serde(rename = "InstanceId")]
+        instance_id: String,
+    }
+
+    // Operations are selected by `Action` on a single path, so the names come from it
+    http_provider!(
+        Ec2Provider,
+        behavior_version: 1,
+        {
+            {
+                path: "/",
+                method: GET,
+                res: Vec<String>,
+                query_params: Filter,
+                static_query: { Action: "DescribeInstances", Version: "2016-11-15" },
+            },
+            {
+                path: "/",
+                method: GET,
+                res: Vec<String>,
+                static_query: { Action: "DescribeRegions", "Version": "2016-11-15" },
+            },
+        }
+    );
+
+    #[tokio::test]
+    async fn test_static_query_precedes_typed_query() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("Action", "DescribeInstances"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(["i-1"]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let provider = Ec2Provider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        let instances = provider
+            .get_describe_instances(&Filter {
+                instance_id: "i-1".to_string(),
+            })
+            .await?;
+        assert_eq!(instances, ["i-1"]);
+
+        let received = mock_server.received_requests().await.unwrap();
+        assert_eq!(
+            received[0].url.query(),
+            Some("Action=DescribeInstances&Version=2016-11-15&InstanceId=i-1")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_operations_share_a_path() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("Action", "DescribeRegions"))
+            .and(query_param("Version", "2016-11-15"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(["eu-west-1"]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let provider = Ec2Provider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        assert_eq!(provider.get_describe_regions().await?, ["eu-west-1"]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use tokio::task::JoinSet;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Item {
+        id: u32,
+    }
+
+    http_provider!(
+        StatsProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/slow",
+                method: GET,
+                fn_name: get_slow,
+                res: Item,
+            },
+            {
+                path: "/missing",
+                method: GET,
+                fn_name: get_missing,
+                res: Item,
+            },
+        }
+    );
+
+    async fn mount(mock_server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(Item { id: 1 })
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_calls_are_counted() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+
+        let provider =
+            StatsProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        assert_eq!(provider.stats(), StatsProviderStats::default());
+
+        let mut calls = JoinSet::new();
+        for _ in 0..8 {
+            let provider = provider.clone();
+            calls.spawn(async move { provider.get_slow().await });
+        }
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(provider.stats().requests_in_flight, 8);
+
+        while let Some(result) = calls.join_next().await {
+            assert_eq!(result??, Item { id: 1 });
+        }
+        assert!(provider.get_missing().await.is_err());
+
+        assert_eq!(
+            provider.stats(),
+            StatsProviderStats {
+                requests_total: 9,
+                requests_failed: 1,
+                requests_in_flight: 0,
+                transport_retries: 0,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_call_leaves_flight() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+
+        let provider =
+            StatsProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let cancelled = tokio::time::timeout(Duration::from_millis(50), provider.get_slow()).await;
+        assert!(cancelled.is_err());
+
+        assert_eq!(
+            provider.stats(),
+            StatsProviderStats {
+                requests_total: 1,
+                requests_failed: 0,
+                requests_in_flight: 0,
+                transport_retries: 0,
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+//! Compiles generated providers under a strict lint set, so expansions stay warning-free
+//! for workspaces building with `-D warnings` and `#![deny(missing_docs)]`.
+#![deny(warnings, missing_docs, unused_mut, unused_variables)]
+
+use http_provider_macro::http_provider;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Path parameters used by the strict provider.
+#[derive(Serialize)]
+pub struct ItemPath {
+    /// Item id
+    pub id: u32,
+}
+
+/// Query parameters used by the strict provider.
+#[derive(Serialize)]
+pub struct ItemQuery {
+    /// Search term
+    pub q: String,
+}
+
+/// Item returned by the strict provider.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Item {
+    /// Item name
+    pub name: String,
+}
+
+http_provider!(
+    StrictProvider,
+    behavior_version: 1,
+    {
+        {
+            path: "/items",
+            method: GET,
+            res: Vec<Item>,
+        },
+        {
+            path: "/items/{id}",
+            method: PUT,
+            path_params: ItemPath,
+            req: Item,
+            res: Item,
+            headers: reqwest::header::HeaderMap,
+            query_params: ItemQuery,
+            retries: 1,
+        },
+        {
+            method: DELETE,
+            fn_name: clear,
+            res: Item,
+        },
+        items: {
+            {
+                path: "/items/{id}",
+                method: GET,
+                fn_name: get,
+                path_params: ItemPath,
+                res: Item,
+            },
+        },
+    }
+);
+
+http_provider!(
+    StrictV2Provider,
+    behavior_version: 2,
+    envelope: { status_field: status, ok_value: "Ok", error_field: error, data_field: result },
+    {
+        {
+            path: "/items",
+            method: GET,
+            res: Vec<Item>,
+            single_flight: true,
+        },
+        {
+            path: "/items/{id}",
+            method: PUT,
+            path_params: ItemPath,
+            req: Item,
+            res: Item,
+            query_params: ItemQuery,
+            retries: 1,
+            keep_raw_body: true,
+        },
+        {
+            path: "/items/{id}/raw",
+            method: GET,
+            path_params: ItemPath,
+            res: Item,
+            deserialize_off_thread: true,
+        },
+        items: {
+            {
+                path: "/items/{id}",
+                method: GET,
+                fn_name: get,
+                path_params: ItemPath,
+                res: Item,
+            },
+        },
+    }
+);
+
+// None of its endpoints deserializes a body, so it has no raw body capture, while the
+// redirect and method override fields it does read are generated
+http_provider!(
+    StrictBinaryProvider,
+    behavior_version: 2,
+    method_override: true,
+    {
+        {
+            path: "/items/{id}",
+            method: DELETE,
+            fn_name: delete,
+            path_params: ItemPath,
+            res: (),
+        },
+        {
+            path: "/items/{id}",
+            method: HEAD,
+            fn_name: exists,
+            path_params: ItemPath,
+        },
+        {
+            path: "/archive.zip",
+            method: GET,
+            fn_name: archive,
+            res_format: bytes,
+        },
+        {
+            path: "/latest",
+            method: GET,
+            fn_name: latest,
+            capture_redirect: true,
+        },
+    }
+);
+
+#[test]
+fn strict_provider_constructs() {
+    let url = reqwest::Url::parse("https://api.example.com/").unwrap();
+    let provider = StrictProvider::new(url, Duration::from_secs(1));
+    let _ = provider.items();
+
+    let url = reqwest::Url::parse("https://api.example.com/").unwrap();
+    let provider = StrictV2Provider::new(url, Duration::from_secs(1));
+    let _ = provider.items();
+
+    let url = reqwest::Url::parse("https://api.example.com/").unwrap();
+    let provider = StrictBinaryProvider::builder(url)
+        .method_override(true)
+        .build()
+        .unwrap();
+    let _ = provider.stats();
+}
@@ -0,0 +1,59 @@
+//! Attributes written before the provider name are copied onto the generated struct.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::Deserialize;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Health {
+        ok: bool,
+    }
+
+    http_provider!(
+        #[doc(hidden)]
+        #[cfg_attr(test, must_use)]
+        // Not applied in tests, where deriving `Debug` would fail
+        #[cfg_attr(not(test), derive(Debug))]
+        pub InternalApi,
+        behavior_version: 1,
+        {
+            { path: "/health", method: GET, res: Health },
+        }
+    );
+
+    http_provider!(
+        #[allow(dead_code)]
+        PlainApi,
+        behavior_version: 1,
+        {
+            { path: "/health", method: GET, res: Health },
+        }
+    );
+
+    #[tokio::test]
+    async fn test_attributed_providers_work_as_usual() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "ok": true })),
+            )
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+
+        let provider = InternalApi::new(url.clone(), Duration::from_secs(5));
+        assert_eq!(provider.clone().get_health().await?, Health { ok: true });
+        let provider = PlainApi::new(url, Duration::from_secs(5));
+        assert_eq!(provider.get_health().await?, Health { ok: true });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+//! Checks shared by the tests of the `codec` options. Each codec's test file invokes
+//! [`codec_tests!`](crate::codec_tests) and keeps only the assertions specific to its format.
+
+use serde::{Deserialize, Serialize};
+use wiremock::{
+    matchers::{header, method, path},
+    Mock, MockServer, Request, Respond, ResponseTemplate,
+};
+
+/// Value sent and received by the codec tests.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    pub id: u64,
+    pub label: String,
+    pub readings: Vec<f32>,
+}
+
+pub fn sample() -> Sample {
+    Sample {
+        id: 7,
+        label: "north".to_string(),
+        readings: vec![1.5, -2.25],
+    }
+}
+
+/// Answers with the request body, declared as the given media type.
+pub struct Echo(pub &'static str);
+
+impl Respond for Echo {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_raw(request.body.clone(), self.0)
+    }
+}
+
+/// Starts a server echoing `/echo` and answering `/latest` with `encoded`, both only to
+/// requests sending and asking for `media_type`.
+pub async fn codec_server(media_type: &'static str, encoded: Vec<u8>) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/echo"))
+        .and(header("content-type", media_type))
+        .and(header("accept", media_type))
+        .respond_with(Echo(media_type))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/latest"))
+        .and(header("accept", media_type))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(encoded, media_type))
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Starts a server answering `/latest` with `sample()` as JSON, whatever the request asks for.
+pub async fn json_server() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/latest"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(sample()))
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Defines `SampleProvider` (version 1) and `SampleV2Provider` (version 2), whose `echo` and
+/// `latest` endpoints use `codec`, and the tests every codec passes: bodies round trip through
+/// both versions, and a JSON body fails to decode while the JSON endpoint of the same provider
+/// still reads it.
+///
+/// `encoded` is an expression encoding `sample()` the way the codec's crate does, with `?`
+/// available.
+#[macro_export]
+macro_rules! codec_tests {
+    ($codec:ident, $media_type:literal, $encoded:expr) => {
+        use $crate::support::codec::{codec_server, json_server, sample, Sample};
+
+        http_provider_macro::http_provider!(
+            SampleProvider,
+            behavior_version: 1,
+            {
+                { path: "/echo", method: POST, fn_name: echo, req: Sample, res: Sample, codec: $codec },
+                { path: "/latest", method: GET, fn_name: latest, res: Sample, codec: $codec, keep_raw_body: true },
+                { path: "/latest", method: GET, fn_name: latest_json, res: Sample },
+            }
+        );
+
+        http_provider_macro::http_provider!(
+            SampleV2Provider,
+            behavior_version: 2,
+            {
+                { path: "/echo", method: POST, fn_name: echo, req: Sample, res: Sample, codec: $codec },
+                { path: "/latest", method: GET, fn_name: latest, res: Sample, codec: $codec },
+            }
+        );
+
+        #[tokio::test]
+        async fn test_bodies_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+            let encoded: Vec<u8> = $encoded;
+            let mock_server = codec_server($media_type, encoded.clone()).await;
+            let url = reqwest::Url::parse(&mock_server.uri())?;
+
+            let provider = SampleProvider::new(url.clone(), std::time::Duration::from_secs(5));
+            assert_eq!(provider.echo(&sample()).await?, sample());
+            let (latest, raw_body) = provider.latest().await?;
+            assert_eq!(latest, sample());
+            assert_eq!(raw_body.as_ref(), encoded.as_slice());
+
+            let provider = SampleV2Provider::new(url, std::time::Duration::from_secs(5));
+            assert_eq!(provider.echo(&sample()).await?, sample());
+            assert_eq!(provider.latest().await?, sample());
+
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn test_undecodable_bodies_fail() -> Result<(), Box<dyn std::error::Error>> {
+            let mock_server = json_server().await;
+            let url = reqwest::Url::parse(&mock_server.uri())?;
+
+            // JSON endpoints of the same provider are unaffected
+            let provider = SampleProvider::new(url.clone(), std::time::Duration::from_secs(5));
+            assert_eq!(provider.latest_json().await?, sample());
+            let error = provider.latest().await.unwrap_err();
+            assert!(
+                error.starts_with("Failed to deserialize response: "),
+                "{}",
+                error
+            );
+
+            let provider = SampleV2Provider::new(url, std::time::Duration::from_secs(5));
+            match provider.latest().await.unwrap_err() {
+                SampleV2ProviderError::Other(message) => {
+                    assert!(message.starts_with("Failed to deserialize response: "))
+                }
+                error => panic!("unexpected error: {:?}", error),
+            }
+
+            Ok(())
+        }
+    };
+}
@@ -0,0 +1,391 @@
+//! Test support shared by the integration tests.
+//!
+//! wiremock covers well-formed request/response exchanges. The scripted server in this module
+//! covers what it can't produce: bodies split at controlled chunk boundaries, pauses in the
+//! middle of a body and connection resets. The fixture helpers produce gzip and brotli encoded
+//! bodies for compression tests. The `codec` module holds the checks shared by the codec
+//! tests.
+//!
+//! Include it from a test file with `mod support;`.
+#![allow(dead_code)]
+
+pub mod codec;
+
+use std::io::Write;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// One step of a scripted response.
+#[derive(Clone, Debug)]
+pub enum Step {
+    /// Write the bytes and flush them, so they leave in their own TCP segment(s)
+    Write(Vec<u8>),
+    /// Pause before the next step
+    Delay(Duration),
+    /// Abort the connection with a TCP reset
+    Reset,
+}
+
+/// A request received by a [`ScriptedServer`].
+#[derive(Clone, Debug)]
+pub struct ReceivedRequest {
+    /// Request line, e.g. `GET /items HTTP/1.1`
+    pub request_line: String,
+    /// Header names (lowercased) and values, in the order received
+    pub headers: Vec<(String, String)>,
+    /// Request body, read according to `Content-Length`
+    pub body: Vec<u8>,
+}
+
+impl ReceivedRequest {
+    /// Returns the value of the first header named `name` (case-insensitive).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_lowercase();
+        self.headers
+            .iter()
+            .find(|(header, _)| *header == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A raw HTTP/1.1 server answering every request with the same script of [`Step`]s.
+///
+/// Each request is read completely before the script starts, and the connection is closed
+/// once the script ends, so every request is served on a fresh connection.
+pub struct ScriptedServer {
+    addr: SocketAddr,
+    received: Arc<Mutex<Vec<ReceivedRequest>>>,
+    task: JoinHandle<()>,
+}
+
+impl ScriptedServer {
+    /// Starts a server on a random local port serving `script` to every request.
+    pub async fn start(script: Vec<Step>) -> Self {
+        Self::start_sequence(vec![script]).await
+    }
+
+    /// Starts a server serving the `n`th script on the `n`th connection, and the last script
+    /// on every connection after them, e.g. to reset only the first connection.
+    pub async fn start_sequence(scripts: Vec<Vec<Step>>) -> Self {
+        assert!(!scripts.is_empty(), "a scripted server needs a script");
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind scripted server");
+        let addr = listener
+            .local_addr()
+            .expect("scripted server has no address");
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let task = tokio::spawn({
+            let received = received.clone();
+            async move {
+                let mut scripts = scripts.into_iter();
+                let mut script = scripts.next().unwrap_or_default();
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(serve(stream, script.clone(), received.clone()));
+                    if let Some(next) = scripts.next() {
+                        script = next;
+                    }
+                }
+            }
+        });
+
+        Self {
+            addr,
+            received,
+            task,
+        }
+    }
+
+    /// Base URI of the server, e.g. `http://127.0.0.1:49152`.
+    pub fn uri(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Base URL of the server, for provider constructors.
+    pub fn url(&self) -> reqwest::Url {
+        self.uri()
+            .parse()
+            .expect("scripted server URI is a valid URL")
+    }
+
+    /// Requests received so far, in arrival order.
+    pub fn received(&self) -> Vec<ReceivedRequest> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+impl Drop for ScriptedServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn serve(
+    mut stream: TcpStream,
+    script: Vec<Step>,
+    received: Arc<Mutex<Vec<ReceivedRequest>>>,
+) {
+    let Some(request) = read_request(&mut stream).await else {
+        return;
+    };
+    received.lock().unwrap().push(request);
+
+    for step in script {
+        match step {
+            Step::Write(bytes) => {
+                if stream.write_all(&bytes).await.is_err() || stream.flush().await.is_err() {
+                    return;
+                }
+            }
+            Step::Delay(duration) => tokio::time::sleep(duration).await,
+            Step::Reset => {
+                // A zero linger timeout makes closing the socket send RST instead of FIN
+                #[allow(deprecated)]
+                let _ = stream.set_linger(Some(Duration::ZERO));
+                return;
+            }
+        }
+    }
+    let _ = stream.shutdown().await;
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<ReceivedRequest> {
+    let mut buffer = Vec::new();
+    let head_end = loop {
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position;
+        }
+        let mut chunk = [0; 1024];
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = buffer[head_end + 4..].to_vec();
+    while body.len() < content_length {
+        let mut chunk = [0; 1024];
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    Some(ReceivedRequest {
+        request_line,
+        headers,
+        body,
+    })
+}
+
+/// Builds the script of a single HTTP response.
+///
+/// ```ignore
+/// let script = ScriptedResponse::new(200)
+///     .header("content-type", "application/json")
+///     .chunked_body([br#"{"na"#.as_slice(), br#"me":"x"}"#])
+///     .delay_between_chunks(Duration::from_millis(50))
+///     .into_script();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScriptedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Body,
+    delay_before_head: Option<Duration>,
+    delay_between_chunks: Option<Duration>,
+    reset_after: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+enum Body {
+    Fixed(Vec<u8>),
+    Chunked(Vec<Vec<u8>>),
+}
+
+impl ScriptedResponse {
+    /// Starts a response with the given status and an empty body.
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Body::Fixed(Vec::new()),
+            delay_before_head: None,
+            delay_between_chunks: None,
+            reset_after: None,
+        }
+    }
+
+    /// Adds a response header.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sends `body` with a `Content-Length` header.
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = Body::Fixed(body.into());
+        self
+    }
+
+    /// Sends a JSON body with a `Content-Length` header.
+    pub fn json_body(self, value: &impl serde::Serialize) -> Self {
+        let body = serde_json::to_vec(value).expect("fixture must serialize");
+        self.header("content-type", "application/json").body(body)
+    }
+
+    /// Sends the body with `Transfer-Encoding: chunked`, one HTTP chunk per item, each written
+    /// separately so the client observes exactly these boundaries.
+    pub fn chunked_body<I, C>(mut self, chunks: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: AsRef<[u8]>,
+    {
+        self.body = Body::Chunked(
+            chunks
+                .into_iter()
+                .map(|chunk| chunk.as_ref().to_vec())
+                .collect(),
+        );
+        self
+    }
+
+    /// Waits before sending the status line, simulating a slow server.
+    pub fn delay_before_head(mut self, delay: Duration) -> Self {
+        self.delay_before_head = Some(delay);
+        self
+    }
+
+    /// Waits between body chunks (or before a fixed body), simulating a stalled download.
+    pub fn delay_between_chunks(mut self, delay: Duration) -> Self {
+        self.delay_between_chunks = Some(delay);
+        self
+    }
+
+    /// Resets the connection once `bytes` body bytes have been sent.
+    pub fn reset_after(mut self, bytes: usize) -> Self {
+        self.reset_after = Some(bytes);
+        self
+    }
+
+    /// Turns the response into the script served by a [`ScriptedServer`].
+    pub fn into_script(self) -> Vec<Step> {
+        let mut steps = Vec::new();
+        if let Some(delay) = self.delay_before_head {
+            steps.push(Step::Delay(delay));
+        }
+
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason(self.status));
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        match &self.body {
+            Body::Fixed(body) => head.push_str(&format!("content-length: {}\r\n", body.len())),
+            Body::Chunked(_) => head.push_str("transfer-encoding: chunked\r\n"),
+        }
+        head.push_str("connection: close\r\n\r\n");
+        steps.push(Step::Write(head.into_bytes()));
+
+        // Body frames as (framing before the data, data, framing after the data)
+        let frames: Vec<(Vec<u8>, Vec<u8>, &[u8])> = match self.body {
+            Body::Fixed(body) => vec![(Vec::new(), body, &[])],
+            Body::Chunked(chunks) => chunks
+                .into_iter()
+                .map(|chunk| {
+                    let prefix = format!("{:x}\r\n", chunk.len()).into_bytes();
+                    (prefix, chunk, &b"\r\n"[..])
+                })
+                .chain(std::iter::once((
+                    b"0\r\n\r\n".to_vec(),
+                    Vec::new(),
+                    &[][..],
+                )))
+                .collect(),
+        };
+
+        let mut sent = 0;
+        for (prefix, data, suffix) in frames {
+            if let Some(delay) = self.delay_between_chunks {
+                steps.push(Step::Delay(delay));
+            }
+            if let Some(limit) = self.reset_after {
+                if sent + data.len() > limit {
+                    let mut partial = prefix;
+                    partial.extend_from_slice(&data[..limit - sent]);
+                    steps.push(Step::Write(partial));
+                    steps.push(Step::Reset);
+                    return steps;
+                }
+            }
+            sent += data.len();
+            let mut frame = prefix;
+            frame.extend_from_slice(&data);
+            frame.extend_from_slice(suffix);
+            steps.push(Step::Write(frame));
+        }
+
+        if self.reset_after.is_some() {
+            steps.push(Step::Reset);
+        }
+        steps
+    }
+}
+
+/// Splits `body` into chunks of at most `size` bytes.
+pub fn split_every(body: &[u8], size: usize) -> Vec<Vec<u8>> {
+    body.chunks(size).map(<[u8]>::to_vec).collect()
+}
+
+/// Returns `body` gzip-encoded, for `Content-Encoding: gzip` fixtures.
+pub fn gzip(body: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(body)
+        .expect("gzip encoding to memory failed");
+    encoder.finish().expect("gzip encoding to memory failed")
+}
+
+/// Returns `body` brotli-encoded, for `Content-Encoding: br` fixtures.
+pub fn brotli(body: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    {
+        let mut encoder = brotli::CompressorWriter::new(&mut encoded, 4096, 5, 22);
+        encoder
+            .write_all(body)
+            .expect("brotli encoding to memory failed");
+    }
+    encoded
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        206 => "Partial Content",
+        404 => "Not Found",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
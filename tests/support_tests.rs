@@ -0,0 +1,140 @@
+//! Exercises the shared test support module against generated providers.
+mod support;
+
+#[cfg(test)]
+mod tests {
+    use crate::support::{brotli, gzip, split_every, ScriptedResponse, ScriptedServer};
+    use http_provider_macro::http_provider;
+    use serde::{Deserialize, Serialize};
+    use std::io::Read;
+    use std::time::Duration;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Report {
+        name: String,
+        values: Vec<u32>,
+    }
+
+    http_provider!(
+        ReportProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/report",
+                method: POST,
+                fn_name: create_report,
+                req: Report,
+                res: Report,
+            },
+        }
+    );
+
+    fn report() -> Report {
+        Report {
+            name: "weekly".to_string(),
+            values: (0..200).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chunk_boundaries_inside_tokens() -> Result<(), Box<dyn std::error::Error>> {
+        let body = serde_json::to_vec(&report())?;
+        let server = ScriptedServer::start(
+            ScriptedResponse::new(200)
+                .header("content-type", "application/json")
+                .chunked_body(split_every(&body, 7))
+                .delay_between_chunks(Duration::from_millis(1))
+                .into_script(),
+        )
+        .await;
+
+        let provider = ReportProvider::new(server.url(), Duration::from_secs(5));
+
+        assert_eq!(provider.create_report(&report()).await?, report());
+
+        let received = server.received();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].request_line, "POST /report HTTP/1.1");
+        assert_eq!(received[0].header("Content-Type"), Some("application/json"));
+        assert_eq!(
+            serde_json::from_slice::<Report>(&received[0].body)?,
+            report()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delayed_response() -> Result<(), Box<dyn std::error::Error>> {
+        let server = ScriptedServer::start(
+            ScriptedResponse::new(200)
+                .json_body(&report())
+                .delay_before_head(Duration::from_millis(300))
+                .into_script(),
+        )
+        .await;
+
+        let provider = ReportProvider::new(server.url(), Duration::from_secs(5));
+
+        let early =
+            tokio::time::timeout(Duration::from_millis(50), provider.create_report(&report()))
+                .await;
+        assert!(early.is_err());
+        assert_eq!(provider.create_report(&report()).await?, report());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_connection_reset_mid_body() -> Result<(), Box<dyn std::error::Error>> {
+        let server = ScriptedServer::start(
+            ScriptedResponse::new(200)
+                .json_body(&report())
+                .reset_after(16)
+                .into_script(),
+        )
+        .await;
+
+        let provider = ReportProvider::new(server.url(), Duration::from_secs(5));
+
+        let error = provider.create_report(&report()).await.unwrap_err();
+        assert!(
+            error.starts_with("Failed to deserialize response"),
+            "unexpected error: {}",
+            error
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_connection_reset_before_response() -> Result<(), Box<dyn std::error::Error>> {
+        let server = ScriptedServer::start(vec![crate::support::Step::Reset]).await;
+
+        let provider = ReportProvider::new(server.url(), Duration::from_secs(5));
+
+        let error = provider.create_report(&report()).await.unwrap_err();
+        assert!(
+            error.starts_with("Request failed"),
+            "unexpected error: {}",
+            error
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compressed_fixtures_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let body = serde_json::to_vec(&report())?;
+
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(gzip(&body).as_slice()).read_to_end(&mut decoded)?;
+        assert_eq!(decoded, body);
+
+        let mut decoded = Vec::new();
+        ::brotli::Decompressor::new(brotli(&body).as_slice(), 4096).read_to_end(&mut decoded)?;
+        assert_eq!(decoded, body);
+
+        Ok(())
+    }
+}
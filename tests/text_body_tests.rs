@@ -0,0 +1,78 @@
+//! `req_format: text` endpoints send a `String` body as is, as `text/plain` by default.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::{header::HeaderMap, Url};
+    use serde::Deserialize;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_string, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Applied {
+        lines: usize,
+    }
+
+    http_provider!(
+        ConfigProvider,
+        behavior_version: 2,
+        {
+            { path: "/config", method: POST, fn_name: apply, req: String, req_format: text, headers: HeaderMap, res: Applied },
+            { path: "/config.yaml", method: PUT, fn_name: apply_yaml, req_format: text, content_type: "application/yaml", res: Applied },
+        }
+    );
+
+    const DOCUMENT: &str = "name: api\nreplicas: 3\n";
+
+    fn applied(lines: usize) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "lines": lines }))
+    }
+
+    #[tokio::test]
+    async fn test_text_is_sent_unchanged() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/config"))
+            .and(header("content-type", "text/plain"))
+            .and(body_string(DOCUMENT))
+            .respond_with(applied(2))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/config"))
+            .and(header("content-type", "text/x-yaml"))
+            .and(body_string(DOCUMENT))
+            .respond_with(applied(0))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/config.yaml"))
+            .and(header("content-type", "application/yaml"))
+            .and(body_string(DOCUMENT))
+            .respond_with(applied(2))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = ConfigProvider::new(url, Duration::from_secs(5));
+        let document = DOCUMENT.to_string();
+
+        assert_eq!(
+            provider.apply(&document, HeaderMap::new()).await?,
+            Applied { lines: 2 }
+        );
+        assert_eq!(provider.apply_yaml(&document).await?, Applied { lines: 2 });
+
+        // A `Content-Type` passed per call still wins
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", "text/x-yaml".parse()?);
+        assert_eq!(
+            provider.apply(&document, headers).await?,
+            Applied { lines: 0 }
+        );
+
+        Ok(())
+    }
+}
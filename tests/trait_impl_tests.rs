@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    trait UserStore {
+        async fn list_users(&self) -> Result<Vec<User>, String>;
+        async fn create_user(&self, body: &User) -> Result<User, String>;
+    }
+
+    trait HealthCheck {
+        async fn ping(&self) -> Result<Health, String>;
+    }
+
+    http_provider!(
+        UserProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/users",
+                method: GET,
+                fn_name: list_users,
+                res: Vec<User>,
+                trait_impl: UserStore,
+            },
+            {
+                path: "/health",
+                method: GET,
+                fn_name: health,
+                res: Health,
+                trait_impl: HealthCheck,
+                trait_fn: ping,
+            },
+            {
+                path: "/users",
+                method: POST,
+                fn_name: create_user,
+                req: User,
+                res: User,
+                trait_impl: UserStore,
+            },
+        }
+    );
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct User {
+        name: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Health {
+        ok: bool,
+    }
+
+    async fn sync_users<S: UserStore>(store: &S) -> Result<Vec<User>, String> {
+        store
+            .create_user(&User {
+                name: "alice".to_string(),
+            })
+            .await?;
+        store.list_users().await
+    }
+
+    #[tokio::test]
+    async fn test_two_method_trait_impl() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+
+        let alice = User {
+            name: "alice".to_string(),
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&alice))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![&alice]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url, Duration::from_secs(5));
+
+        let users = sync_users(&provider).await?;
+        assert_eq!(users, vec![alice]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_trait_fn_delegates_to_inherent_method() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Health { ok: true }))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url, Duration::from_secs(5));
+
+        assert_eq!(provider.health().await?, Health { ok: true });
+        assert_eq!(HealthCheck::ping(&provider).await?, Health { ok: true });
+
+        Ok(())
+    }
+
+    mod also_inherent {
+        use super::*;
+
+        trait Fetcher {
+            async fn fetch(&self) -> Result<Health, String>;
+        }
+
+        http_provider!(
+            InherentProvider,
+            behavior_version: 1,
+            also_inherent: true,
+            {
+                {
+                    path: "/health",
+                    method: GET,
+                    fn_name: fetch,
+                    res: Health,
+                    trait_impl: Fetcher,
+                },
+            }
+        );
+
+        #[tokio::test]
+        async fn test_inherent_and_trait_access() -> Result<(), Box<dyn std::error::Error>> {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/health"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(Health { ok: true }))
+                .expect(2)
+                .mount(&mock_server)
+                .await;
+
+            let url = Url::from_str(&mock_server.uri())?;
+            let provider = InherentProvider::new(url, Duration::from_secs(5));
+
+            assert_eq!(provider.fetch().await?, Health { ok: true });
+            assert_eq!(
+                <InherentProvider as Fetcher>::fetch(&provider).await?,
+                Health { ok: true }
+            );
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::future::Future;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    /// A native async trait whose futures must be `Send`.
+    trait UserStore {
+        fn list_users(&self) -> impl Future<Output = Result<Vec<User>, String>> + Send;
+    }
+
+    /// An `async_trait` trait, usable as a trait object.
+    #[async_trait]
+    trait UserCount {
+        async fn count_users(&self) -> Result<u32, String>;
+    }
+
+    /// An `async_trait` trait without the `Send` requirement.
+    #[async_trait(?Send)]
+    trait LocalHealth {
+        async fn ping(&self) -> Result<bool, String>;
+    }
+
+    http_provider!(
+        UserProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/users",
+                method: GET,
+                fn_name: list_users,
+                res: Vec<User>,
+                trait_impl: UserStore,
+                trait_style: afit,
+            },
+            {
+                path: "/users/count",
+                method: GET,
+                fn_name: count_users,
+                res: u32,
+                trait_impl: UserCount,
+                trait_style: async_trait,
+            },
+            {
+                path: "/health",
+                method: GET,
+                fn_name: health,
+                res: bool,
+                trait_impl: LocalHealth,
+                trait_fn: ping,
+                trait_style: async_trait(?Send),
+            },
+        }
+    );
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct User {
+        name: String,
+    }
+
+    async fn provider(
+        request_path: &str,
+        body: impl Serialize,
+    ) -> Result<(MockServer, UserProvider), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(request_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UserProvider::new(url, Duration::from_secs(5));
+        Ok((mock_server, provider))
+    }
+
+    #[tokio::test]
+    async fn test_afit_future_is_send() -> Result<(), Box<dyn std::error::Error>> {
+        let alice = User {
+            name: "alice".to_string(),
+        };
+        let (_mock_server, provider) = provider("/users", [&alice]).await?;
+
+        // Spawning only compiles because the impl satisfies the trait's `Send` bound
+        fn spawn_list<S: UserStore + Send + Sync + 'static>(
+            store: Arc<S>,
+        ) -> tokio::task::JoinHandle<Result<Vec<User>, String>> {
+            tokio::spawn(async move { store.list_users().await })
+        }
+
+        let users = spawn_list(Arc::new(provider)).await??;
+        assert_eq!(users, [alice]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_async_trait_object() -> Result<(), Box<dyn std::error::Error>> {
+        let (_mock_server, provider) = provider("/users/count", 3).await?;
+
+        let store: Arc<dyn UserCount + Send + Sync> = Arc::new(provider);
+        let count = tokio::spawn(async move { store.count_users().await }).await??;
+        assert_eq!(count, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_async_trait_without_send() -> Result<(), Box<dyn std::error::Error>> {
+        let (_mock_server, provider) = provider("/health", true).await?;
+
+        let health: Box<dyn LocalHealth> = Box::new(provider);
+        assert!(health.ping().await?);
+
+        Ok(())
+    }
+}
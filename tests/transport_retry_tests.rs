@@ -0,0 +1,95 @@
+//! `retry_transport_errors_once` resends a request of an idempotent endpoint once when the
+//! connection failed before any response arrived. The scripted server resets connections,
+//! which wiremock can't.
+mod support;
+
+#[cfg(test)]
+mod tests {
+    use crate::support::{ScriptedResponse, ScriptedServer, Step};
+    use http_provider_macro::http_provider;
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        id: u32,
+    }
+
+    http_provider!(
+        RetryingProvider,
+        behavior_version: 2,
+        retry_transport_errors_once: true,
+        {
+            { path: "/items", method: GET, fn_name: get_item, res: Item },
+            { path: "/items", method: PUT, fn_name: put_item, req: Item, res: Item },
+            { path: "/items", method: POST, fn_name: create_item, req: Item, res: Item },
+        }
+    );
+
+    http_provider!(
+        PlainProvider,
+        behavior_version: 2,
+        {
+            { path: "/items", method: GET, fn_name: get_item, res: Item },
+        }
+    );
+
+    fn item() -> ScriptedResponse {
+        ScriptedResponse::new(200).json_body(&Item { id: 1 })
+    }
+
+    #[tokio::test]
+    async fn test_reset_connection_is_retried_once() -> Result<(), Box<dyn std::error::Error>> {
+        let server =
+            ScriptedServer::start_sequence(vec![vec![Step::Reset], item().into_script()]).await;
+        let provider = RetryingProvider::new(server.url(), Duration::from_secs(5));
+
+        assert_eq!(provider.get_item().await?, Item { id: 1 });
+        assert_eq!(server.received().len(), 2);
+        assert_eq!(provider.stats().transport_retries, 1);
+
+        let server =
+            ScriptedServer::start_sequence(vec![vec![Step::Reset], item().into_script()]).await;
+        let provider = RetryingProvider::new(server.url(), Duration::from_secs(5));
+        assert_eq!(provider.put_item(&Item { id: 1 }).await?, Item { id: 1 });
+        let received = server.received();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[1].body, received[0].body);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_other_failures_are_not_retried() -> Result<(), Box<dyn std::error::Error>> {
+        // Only once
+        let server = ScriptedServer::start(vec![Step::Reset]).await;
+        let provider = RetryingProvider::new(server.url(), Duration::from_secs(5));
+        assert!(provider.get_item().await.is_err());
+        assert_eq!(server.received().len(), 2);
+        assert_eq!(provider.stats().transport_retries, 1);
+
+        // Not for methods that aren't idempotent
+        let server =
+            ScriptedServer::start_sequence(vec![vec![Step::Reset], item().into_script()]).await;
+        let provider = RetryingProvider::new(server.url(), Duration::from_secs(5));
+        assert!(provider.create_item(&Item { id: 1 }).await.is_err());
+        assert_eq!(server.received().len(), 1);
+
+        // Not for responses, even error ones
+        let server = ScriptedServer::start(ScriptedResponse::new(503).into_script()).await;
+        let provider = RetryingProvider::new(server.url(), Duration::from_secs(5));
+        assert!(provider.get_item().await.is_err());
+        assert_eq!(server.received().len(), 1);
+        assert_eq!(provider.stats().transport_retries, 0);
+
+        // Not without the option
+        let server =
+            ScriptedServer::start_sequence(vec![vec![Step::Reset], item().into_script()]).await;
+        let provider = PlainProvider::new(server.url(), Duration::from_secs(5));
+        assert!(provider.get_item().await.is_err());
+        assert_eq!(server.received().len(), 1);
+        assert_eq!(provider.stats().transport_retries, 0);
+
+        Ok(())
+    }
+}
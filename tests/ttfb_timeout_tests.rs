@@ -0,0 +1,162 @@
+//! `ttfb_timeout_ms` bounds the wait for the response headers only, and version 2 doesn't apply
+//! the provider's timeout to streamed bodies. The scripted server controls when the head and
+//! each body chunk are sent, which wiremock can't.
+mod support;
+
+#[cfg(test)]
+mod tests {
+    use crate::support::{ScriptedResponse, ScriptedServer};
+    use futures::StreamExt;
+    use http_provider_macro::http_provider;
+    use std::time::Duration;
+
+    http_provider!(
+        FeedProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/feed",
+                method: GET,
+                fn_name: feed,
+                res: Vec<u32>,
+                ttfb_timeout_ms: 100,
+            },
+            {
+                path: "/feed",
+                method: GET,
+                fn_name: feed_with_retry,
+                res: Vec<u32>,
+                ttfb_timeout_ms: 100,
+                retries: 1,
+            },
+        }
+    );
+
+    http_provider!(
+        FeedV2Provider,
+        behavior_version: 2,
+        {
+            { path: "/feed", method: GET, fn_name: feed, res: Vec<u32> },
+            { path: "/feed", method: GET, fn_name: feed_bounded, res: Vec<u32>, ttfb_timeout_ms: 100 },
+            { path: "/feed", method: GET, fn_name: feed_lines, res: u32, stream: ndjson },
+            { path: "/feed", method: GET, fn_name: feed_download, download: true },
+        }
+    );
+
+    #[tokio::test]
+    async fn test_delayed_head_times_out() {
+        let server = ScriptedServer::start(
+            ScriptedResponse::new(200)
+                .header("content-type", "application/json")
+                .body("[1]")
+                .delay_before_head(Duration::from_millis(500))
+                .into_script(),
+        )
+        .await;
+        let provider = FeedProvider::new(server.url(), Duration::from_secs(5));
+
+        let error = provider.feed().await.unwrap_err();
+        assert_eq!(
+            error,
+            "Timed out after 100 ms waiting for the response headers"
+        );
+
+        // A timed out attempt is retried like a transport error
+        let error = provider.feed_with_retry().await.unwrap_err();
+        assert_eq!(
+            error,
+            "Timed out after 100 ms waiting for the response headers"
+        );
+        assert_eq!(server.received().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_version_2_delayed_head_is_a_timeout() {
+        let server = ScriptedServer::start(
+            ScriptedResponse::new(200)
+                .header("content-type", "application/json")
+                .body("[1]")
+                .delay_before_head(Duration::from_millis(500))
+                .into_script(),
+        )
+        .await;
+        let provider = FeedV2Provider::new(server.url(), Duration::from_secs(5));
+
+        let error = provider.feed_bounded().await.unwrap_err();
+        match &error {
+            FeedV2ProviderError::Timeout {
+                phase: "response headers",
+                limit,
+            } => assert_eq!(*limit, Duration::from_millis(100)),
+            error => panic!("unexpected error: {:?}", error),
+        }
+        assert_eq!(
+            error.to_string(),
+            "Timed out after 100 ms waiting for the response headers"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slow_body_is_not_cut_off() -> Result<(), Box<dyn std::error::Error>> {
+        // The head arrives at once, the body trickles in over ~400 ms
+        let server = ScriptedServer::start(
+            ScriptedResponse::new(200)
+                .header("content-type", "application/json")
+                .chunked_body([
+                    b"[1".as_slice(),
+                    b",2".as_slice(),
+                    b",3".as_slice(),
+                    b",4".as_slice(),
+                    b"]".as_slice(),
+                ])
+                .delay_between_chunks(Duration::from_millis(100))
+                .into_script(),
+        )
+        .await;
+        let provider = FeedProvider::new(server.url(), Duration::from_secs(5));
+
+        assert_eq!(provider.feed().await?, [1, 2, 3, 4]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_version_2_streams_outlast_provider_timeout(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // The body trickles in over ~300 ms, twice the provider's timeout
+        let server = ScriptedServer::start(
+            ScriptedResponse::new(200)
+                .header("content-type", "application/x-ndjson")
+                .chunked_body([
+                    b"1\n".as_slice(),
+                    b"2\n".as_slice(),
+                    b"3\n".as_slice(),
+                    b"4\n".as_slice(),
+                ])
+                .delay_between_chunks(Duration::from_millis(100))
+                .into_script(),
+        )
+        .await;
+        let provider = FeedV2Provider::new(server.url(), Duration::from_millis(150));
+
+        let lines: Vec<_> = provider.feed_lines().await?.collect().await;
+        assert_eq!(
+            lines.into_iter().collect::<Result<Vec<_>, _>>()?,
+            [1, 2, 3, 4]
+        );
+
+        let mut written = Vec::new();
+        provider.feed_download(&mut written).await?;
+        assert_eq!(written, b"1\n2\n3\n4\n");
+
+        // A buffered body is still bounded by the provider's timeout
+        let error = provider.feed().await.unwrap_err();
+        assert!(
+            matches!(error, FeedV2ProviderError::BodyTransfer { .. }),
+            "{:?}",
+            error
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+//! Endpoint types written as aliases, associated types and fully qualified generic paths,
+//! in every position the generated code uses them. Compiling this file is most of the test.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_json, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct UserPath {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct Page {
+        page: u32,
+    }
+
+    /// Describes an API's types, so endpoints name them through the implementing type.
+    trait ApiSpec {
+        type UserList: DeserializeOwned;
+        type NewUser: Serialize;
+        type Query: Serialize;
+        type Path: Serialize;
+    }
+
+    struct MyApi;
+
+    impl ApiSpec for MyApi {
+        type UserList = Vec<User>;
+        type NewUser = User;
+        type Query = Page;
+        type Path = UserPath;
+    }
+
+    type Users = Vec<User>;
+    type Keyed<T> = BTreeMap<String, T>;
+
+    http_provider!(
+        TypePathProvider,
+        behavior_version: 1,
+        {
+            {
+                path: "/users",
+                method: GET,
+                fn_name: list_associated,
+                res: <MyApi as ApiSpec>::UserList,
+                query_params: <MyApi as ApiSpec>::Query,
+            },
+            {
+                path: "/users",
+                method: POST,
+                fn_name: create_associated,
+                req: <MyApi as ApiSpec>::NewUser,
+                res: <MyApi as ApiSpec>::NewUser,
+            },
+            {
+                path: "/users/{id}",
+                method: GET,
+                fn_name: get_associated,
+                path_params: <MyApi as ApiSpec>::Path,
+                res: <MyApi as ApiSpec>::NewUser,
+            },
+            {
+                path: "/users",
+                method: GET,
+                fn_name: list_alias,
+                res: Users,
+            },
+            {
+                path: "/keyed",
+                method: GET,
+                fn_name: keyed_alias,
+                res: Keyed<User>,
+            },
+            {
+                path: "/keyed",
+                method: GET,
+                fn_name: keyed_qualified,
+                res: ::std::collections::BTreeMap<::std::string::String, self::User>,
+            },
+            {
+                path: "/users",
+                method: GET,
+                fn_name: list_shared,
+                res: <MyApi as ApiSpec>::UserList,
+                single_flight: true,
+            },
+            {
+                path: "/users",
+                method: GET,
+                fn_name: list_raw,
+                res: <MyApi as ApiSpec>::UserList,
+                keep_raw_body: true,
+            },
+            {
+                path: "/users",
+                method: GET,
+                fn_name: list_off_thread,
+                res: <MyApi as ApiSpec>::UserList,
+                deserialize_off_thread: true,
+            },
+            {
+                path: "/wrapped",
+                method: GET,
+                fn_name: wrapped_associated,
+                res: <MyApi as ApiSpec>::UserList,
+                envelope: { status_field: status, ok_value: "Ok", error_field: error, data_field: result },
+            },
+            {
+                path: "/users",
+                method: GET,
+                fn_name: list_emitted,
+                res: <MyApi as ApiSpec>::UserList,
+                emit: Counter,
+            },
+        }
+    );
+
+    // Version 2 buffers and decodes the body on a different path
+    http_provider!(
+        TypePathV2Provider,
+        behavior_version: 2,
+        {
+            {
+                path: "/users/{id}",
+                method: POST,
+                fn_name: replace,
+                path_params: <MyApi as ApiSpec>::Path,
+                req: <MyApi as ApiSpec>::NewUser,
+                query_params: <MyApi as ApiSpec>::Query,
+                res: Keyed<<MyApi as ApiSpec>::NewUser>,
+            },
+        }
+    );
+
+    #[derive(Default)]
+    struct Counter(std::sync::atomic::AtomicUsize);
+
+    impl TypePathProviderResponseSink<<MyApi as ApiSpec>::UserList> for Counter {
+        fn accept(&self, response: &Vec<User>) {
+            self.0
+                .fetch_add(response.len(), std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    async fn mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        let users = serde_json::json!([{ "id": 1 }, { "id": 2 }]);
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{ "id": 3 }])),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(users.clone()))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .and(body_json(serde_json::json!({ "id": 9 })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 9 })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 5 })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/users/5"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "a": { "id": 1 } })),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/keyed"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "a": { "id": 1 } })),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/wrapped"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "status": "Ok", "error": null, "result": users }),
+            ))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_associated_types() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            TypePathProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        let users: Vec<User> = provider.list_associated(&Page { page: 2 }).await?;
+        assert_eq!(users, [User { id: 3 }]);
+
+        let created = provider.create_associated(&User { id: 9 }).await?;
+        assert_eq!(created, User { id: 9 });
+
+        let user = provider.get_associated(&UserPath { id: 5 }).await?;
+        assert_eq!(user, User { id: 5 });
+
+        let expected = vec![User { id: 1 }, User { id: 2 }];
+        assert_eq!(provider.list_shared().await?, expected);
+        assert_eq!(provider.list_raw().await?.0, expected);
+        assert_eq!(provider.list_off_thread().await?, expected);
+        assert_eq!(provider.wrapped_associated().await?, expected);
+
+        let provider = provider.with_sink(Counter::default());
+        assert_eq!(provider.list_emitted().await?, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_aliases_and_qualified_paths() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = mock_server().await;
+        let provider =
+            TypePathProvider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+
+        assert_eq!(provider.list_alias().await?.len(), 2);
+
+        let expected = BTreeMap::from([("a".to_string(), User { id: 1 })]);
+        assert_eq!(provider.keyed_alias().await?, expected);
+        assert_eq!(provider.keyed_qualified().await?, expected);
+
+        let provider =
+            TypePathV2Provider::new(Url::from_str(&mock_server.uri())?, Duration::from_secs(5));
+        let replaced = provider
+            .replace(&UserPath { id: 5 }, &User { id: 5 }, &Page { page: 1 })
+            .await?;
+        assert_eq!(
+            replaced,
+            BTreeMap::from([("a".to_string(), User { id: 1 })])
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    behavior_version: 2,
+    {
+        {
+            path: "/health",
+            method: GET,
+            fn_name: health,
+            res: String,
+            args_struct: true,
+        }
+    }
+);
+
+fn main() {}
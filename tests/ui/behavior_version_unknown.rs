@@ -0,0 +1,15 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    behavior_version: 3,
+    {
+        {
+            path: "/users",
+            method: GET,
+            res: Vec<String>,
+        }
+    }
+);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/transfers",
+            method: POST,
+            res: String,
+            canonical_json: true,
+        }
+    }
+);
+
+fn main() {}
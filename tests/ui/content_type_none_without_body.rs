@@ -0,0 +1,15 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/ping",
+            method: POST,
+            res: String,
+            content_type: none,
+        },
+    }
+);
+
+fn main() {}
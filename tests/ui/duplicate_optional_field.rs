@@ -0,0 +1,16 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/users",
+            method: GET,
+            fn_name: list_users,
+            res: Vec<String>,
+            fn_name: get_users,
+        }
+    }
+);
+
+fn main() {}
@@ -0,0 +1,16 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    api_version: "2024-06-01",
+    api_version: "2024-07-01",
+    {
+        {
+            path: "/users",
+            method: GET,
+            res: Vec<String>,
+        }
+    }
+);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/users",
+            method: GET,
+            res: Vec<String>,
+            res: String,
+        }
+    }
+);
+
+fn main() {}
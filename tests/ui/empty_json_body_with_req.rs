@@ -0,0 +1,16 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/trigger",
+            method: POST,
+            req: String,
+            res: String,
+            empty_json_body: true,
+        }
+    }
+);
+
+fn main() {}
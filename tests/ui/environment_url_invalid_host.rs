@@ -0,0 +1,19 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    behavior_version: 2,
+    environments: {
+        dev: "https://dev.api.example.com/",
+        prod: "https://api^example.com/",
+    },
+    {
+        {
+            path: "/users",
+            method: GET,
+            res: String,
+        }
+    }
+);
+
+fn main() {}
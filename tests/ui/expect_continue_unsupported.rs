@@ -0,0 +1,16 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/uploads",
+            method: POST,
+            req: Vec<u8>,
+            res: String,
+            expect_continue: true,
+        }
+    }
+);
+
+fn main() {}
@@ -0,0 +1,20 @@
+use http_provider_macro::http_provider;
+
+struct TokenRequest {}
+
+http_provider!(
+    Provider,
+    behavior_version: 2,
+    {
+        {
+            path: "/oauth/token",
+            method: POST,
+            fn_name: token,
+            form: TokenRequest,
+            res: String,
+            req: TokenRequest,
+        },
+    }
+);
+
+fn main() {}
@@ -0,0 +1,25 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    behavior_version: 1,
+    {
+        {
+            path: "/reports",
+            method: GET,
+            fn_name: report,
+            res: String,
+        },
+    }
+);
+
+// Only providers with `res_headers` endpoints get the trait
+struct Tag;
+
+impl ProviderFromHeader for Tag {
+    fn from_header(_: &reqwest::header::HeaderValue) -> Result<Self, String> {
+        Ok(Tag)
+    }
+}
+
+fn main() {}
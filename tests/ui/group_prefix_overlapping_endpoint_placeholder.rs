@@ -0,0 +1,28 @@
+use http_provider_macro::http_provider;
+
+struct OrgPath {
+    org_id: u32,
+}
+
+struct MemberPath {
+    org_id: u32,
+}
+
+http_provider!(
+    Provider,
+    {
+        orgs: {
+            prefix: "/orgs/{org_id}",
+            group_params: OrgPath,
+            {
+                path: "/members/{org_id}",
+                method: GET,
+                fn_name: member,
+                path_params: MemberPath,
+                res: String,
+            },
+        },
+    }
+);
+
+fn main() {}
@@ -0,0 +1,30 @@
+use http_provider_macro::http_provider;
+
+struct OrgPath {
+    id: u32,
+}
+
+struct RepoPath {
+    id: u32,
+}
+
+http_provider!(
+    Provider,
+    {
+        orgs: {
+            prefix: "/orgs/{id}",
+            group_params: OrgPath,
+            repos: {
+                prefix: "/repos/{id}",
+                group_params: RepoPath,
+                {
+                    method: GET,
+                    fn_name: get,
+                    res: String,
+                },
+            },
+        },
+    }
+);
+
+fn main() {}
@@ -0,0 +1,18 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        orgs: {
+            prefix: "/orgs/{org_id}",
+            {
+                path: "/members",
+                method: GET,
+                fn_name: members,
+                res: Vec<String>,
+            },
+        },
+    }
+);
+
+fn main() {}
@@ -0,0 +1,20 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/",
+            method: GET,
+            res: String,
+            query_params: Vec<(String, String)>,
+        },
+        {
+            path: "/",
+            method: GET,
+            res: String,
+        }
+    }
+);
+
+fn main() {}
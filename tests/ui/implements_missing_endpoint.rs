@@ -0,0 +1,23 @@
+use http_provider_macro::http_provider;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct User {
+    id: u32,
+}
+
+trait ApiContract {
+    async fn list_users(&self) -> Result<Vec<User>, String>;
+    async fn delete_user(&self) -> Result<(), String>;
+}
+
+http_provider!(
+    UserProvider,
+    behavior_version: 1,
+    implements: ApiContract,
+    {
+        { path: "/users", method: GET, fn_name: list_users, res: Vec<User> },
+    }
+);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    api_version: "2024-01-01\n",
+    {
+        {
+            path: "/items",
+            method: GET,
+            res: String,
+        },
+    }
+);
+
+fn main() {}
@@ -0,0 +1,21 @@
+#![deny(deprecated)]
+
+use http_provider_macro::http_provider;
+
+http_provider!(
+    LegacyProvider,
+    behavior_version: 1,
+    legacy_ctor: true,
+    {
+        {
+            path: "/health",
+            method: GET,
+            res: String,
+        }
+    }
+);
+
+fn main() {
+    let url = reqwest::Url::parse("http://localhost").unwrap();
+    let _ = LegacyProvider::new(url, Some(5000));
+}
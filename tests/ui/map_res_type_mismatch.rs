@@ -0,0 +1,26 @@
+use http_provider_macro::http_provider;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Envelope {
+    payload: Payload,
+}
+
+#[derive(Deserialize)]
+struct Payload {
+    id: u32,
+}
+
+fn unwrap_payload(envelope: Envelope) -> Result<Payload, String> {
+    Ok(envelope.payload)
+}
+
+http_provider!(
+    PayloadProvider,
+    behavior_version: 2,
+    {
+        { path: "/payload", method: GET, res: Envelope, map_res: unwrap_payload -> u32 },
+    }
+);
+
+fn main() {}
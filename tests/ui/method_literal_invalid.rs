@@ -0,0 +1,14 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/cache",
+            method: "PURGE ALL",
+            res: String,
+        }
+    }
+);
+
+fn main() {}
@@ -0,0 +1,19 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/a",
+            method: GET,
+            res: String,
+        }
+        {
+            path: "/b",
+            method: GET,
+            res: String,
+        }
+    }
+);
+
+fn main() {}
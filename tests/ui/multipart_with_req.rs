@@ -0,0 +1,19 @@
+use http_provider_macro::http_provider;
+
+struct Meta {}
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/documents",
+            method: POST,
+            fn_name: upload,
+            req: Meta,
+            res: String,
+            multipart: { files: many },
+        },
+    }
+);
+
+fn main() {}
@@ -0,0 +1,16 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    behavior_version: 1,
+    {
+        {
+            path: "/files//raw",
+            method: GET,
+            res: String,
+            preserve_path: true,
+        }
+    }
+);
+
+fn main() {}
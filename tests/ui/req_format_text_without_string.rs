@@ -0,0 +1,17 @@
+use http_provider_macro::http_provider;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Applied {
+    lines: usize,
+}
+
+http_provider!(
+    ConfigProvider,
+    behavior_version: 2,
+    {
+        { path: "/config", method: POST, req: Vec<u8>, req_format: text, res: Applied },
+    }
+);
+
+fn main() {}
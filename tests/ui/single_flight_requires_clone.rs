@@ -0,0 +1,20 @@
+use http_provider_macro::http_provider;
+
+#[derive(serde::Deserialize)]
+struct Summary {}
+
+http_provider!(
+    Provider,
+    behavior_version: 1,
+    {
+        {
+            path: "/summary",
+            method: GET,
+            fn_name: get_summary,
+            res: Summary,
+            single_flight: true,
+        },
+    }
+);
+
+fn main() {}
@@ -0,0 +1,16 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/reports",
+            method: POST,
+            fn_name: create_report,
+            res: String,
+            single_flight: true,
+        },
+    }
+);
+
+fn main() {}
@@ -0,0 +1,18 @@
+use http_provider_macro::http_provider;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Health {
+    ok: bool,
+}
+
+http_provider!(
+    #[derive(Clone)]
+    pub HealthApi,
+    behavior_version: 1,
+    {
+        { path: "/health", method: GET, res: Health },
+    }
+);
+
+fn main() {}
@@ -0,0 +1,17 @@
+use http_provider_macro::http_provider;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Health {
+    ok: bool,
+}
+
+http_provider!(
+    pub(crate) HealthApi,
+    behavior_version: 1,
+    {
+        { path: "/health", method: GET, res: Health },
+    }
+);
+
+fn main() {}
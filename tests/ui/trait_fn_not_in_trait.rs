@@ -0,0 +1,22 @@
+use http_provider_macro::http_provider;
+
+trait Fetcher {
+    async fn fetch(&self) -> Result<String, String>;
+}
+
+http_provider!(
+    Provider,
+    behavior_version: 1,
+    {
+        {
+            path: "/fetch",
+            method: GET,
+            fn_name: fetch,
+            trait_fn: fetch_value,
+            res: String,
+            trait_impl: Fetcher,
+        }
+    }
+);
+
+fn main() {}
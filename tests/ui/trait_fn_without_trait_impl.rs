@@ -0,0 +1,16 @@
+use http_provider_macro::http_provider;
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/fetch",
+            method: GET,
+            fn_name: fetch,
+            trait_fn: fetch_value,
+            res: String,
+        }
+    }
+);
+
+fn main() {}
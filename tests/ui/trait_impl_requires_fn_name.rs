@@ -0,0 +1,19 @@
+use http_provider_macro::http_provider;
+
+trait Fetcher {
+    async fn fetch(&self) -> Result<String, String>;
+}
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/fetch",
+            method: GET,
+            res: String,
+            trait_impl: Fetcher,
+        }
+    }
+);
+
+fn main() {}
@@ -0,0 +1,29 @@
+use http_provider_macro::http_provider;
+
+trait UserStore {
+    async fn list_users(&self) -> Result<Vec<String>, String>;
+    async fn count_users(&self) -> Result<u32, String>;
+}
+
+http_provider!(
+    Provider,
+    {
+        {
+            path: "/users",
+            method: GET,
+            fn_name: list_users,
+            res: Vec<String>,
+            trait_impl: UserStore,
+        },
+        {
+            path: "/users/count",
+            method: GET,
+            fn_name: count_users,
+            res: u32,
+            trait_impl: UserStore,
+            trait_style: async_trait,
+        },
+    }
+);
+
+fn main() {}
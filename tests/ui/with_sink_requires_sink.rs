@@ -0,0 +1,31 @@
+use http_provider_macro::http_provider;
+
+#[derive(serde::Deserialize)]
+struct Order {}
+
+struct Outbox;
+
+impl ProviderResponseSink<Order> for Outbox {
+    fn accept(&self, _order: &Order) {}
+}
+
+struct Ledger;
+
+http_provider!(
+    Provider,
+    behavior_version: 1,
+    {
+        {
+            path: "/orders",
+            method: GET,
+            fn_name: get_order,
+            res: Order,
+            emit: Outbox,
+        },
+    }
+);
+
+fn main() {
+    let url = reqwest::Url::parse("http://localhost").unwrap();
+    let _ = Provider::new(url, std::time::Duration::from_secs(5)).with_sink(Ledger);
+}
@@ -0,0 +1,110 @@
+//! `upload_stream` endpoints send any `impl Into<reqwest::Body>`, such as a stream, as the
+//! request body, without serializing or buffering it.
+#[cfg(test)]
+mod tests {
+    use http_provider_macro::http_provider;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{body_bytes, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Upload {
+        size: u64,
+    }
+
+    #[derive(Serialize)]
+    struct UploadPath {
+        name: String,
+    }
+
+    http_provider!(
+        UploadProvider,
+        behavior_version: 1,
+        {
+            { path: "/uploads/{name}", method: PUT, fn_name: upload, path_params: UploadPath, upload_stream: true, res: Upload },
+            { path: "/uploads/{name}", method: PUT, fn_name: upload_args, path_params: UploadPath, upload_stream: true, res: Upload, args_struct: true },
+            { path: "/logs", method: POST, fn_name: append_log, upload_stream: true, content_type: "text/plain", res: Upload },
+        }
+    );
+
+    fn payload() -> Vec<u8> {
+        (0..50_000u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn payload_stream() -> reqwest::Body {
+        let chunks: Vec<Result<Vec<u8>, std::io::Error>> = payload()
+            .chunks(4096)
+            .map(|chunk| Ok(chunk.to_vec()))
+            .collect();
+        reqwest::Body::wrap_stream(futures::stream::iter(chunks))
+    }
+
+    fn upload_path() -> UploadPath {
+        UploadPath {
+            name: "data.bin".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_is_sent_as_the_body() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let length = payload().len().to_string();
+        Mock::given(method("PUT"))
+            .and(path("/uploads/data.bin"))
+            .and(header("content-type", "application/octet-stream"))
+            .and(header("content-length", length.as_str()))
+            .and(body_bytes(payload()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Upload { size: 50_000 }))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UploadProvider::new(url, Duration::from_secs(5));
+
+        let upload = provider
+            .upload(&upload_path(), payload_stream(), Some(50_000))
+            .await?;
+        assert_eq!(upload.size, 50_000);
+
+        // Anything converting into a body works, including a buffer
+        let upload = provider
+            .upload(&upload_path(), payload(), Some(50_000))
+            .await?;
+        assert_eq!(upload.size, 50_000);
+
+        let upload = provider
+            .upload_args(UploadProviderUploadArgsArgs {
+                path: upload_path(),
+                body: payload_stream(),
+                content_length: Some(50_000),
+            })
+            .await?;
+        assert_eq!(upload.size, 50_000);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unknown_lengths_are_sent_chunked() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/logs"))
+            .and(header("content-type", "text/plain"))
+            .and(header("transfer-encoding", "chunked"))
+            .and(body_bytes(payload()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Upload { size: 50_000 }))
+            .mount(&mock_server)
+            .await;
+        let url = Url::from_str(&mock_server.uri())?;
+        let provider = UploadProvider::new(url, Duration::from_secs(5));
+
+        let upload = provider.append_log(payload_stream(), None).await?;
+        assert_eq!(upload.size, 50_000);
+
+        Ok(())
+    }
+}
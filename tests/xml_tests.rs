@@ -0,0 +1,29 @@
+//! `codec: xml` endpoints send and decode XML bodies. Run with
+//! `cargo test --features xml`.
+#![cfg(feature = "xml")]
+
+mod support;
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    const DOCUMENT: &str =
+        "<Sample><id>7</id><label>north</label><readings>1.5</readings><readings>-2.25</readings></Sample>";
+
+    crate::codec_tests!(xml, "application/xml", DOCUMENT.as_bytes().to_vec());
+
+    #[tokio::test]
+    async fn test_error_quotes_document() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = json_server().await;
+        let url = reqwest::Url::parse(&mock_server.uri())?;
+        let provider = SampleProvider::new(url, Duration::from_secs(5));
+
+        // The error quotes the document, since XML errors don't say where they happened
+        let error = provider.latest().await.unwrap_err();
+        let snippet = format!("in XML `{}`", serde_json::to_string(&sample())?);
+        assert!(error.ends_with(&snippet), "{}", error);
+
+        Ok(())
+    }
+}